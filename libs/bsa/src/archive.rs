@@ -0,0 +1,204 @@
+// rust std imports
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// external imports
+use bytes_io::Reader;
+use encoding_rs::WINDOWS_1252;
+use hashbrown::HashMap;
+
+// internal imports
+use crate::{normalize_name, FileHash};
+
+/// A file stored in an archive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchiveEntry {
+    /// The file name relative to `Data Files`, as written in the archive.
+    pub name: String,
+    pub hash: FileHash,
+    /// The position of the file's contents from the start of the archive.
+    pub offset: u64,
+    pub size: usize,
+}
+
+/// A `.bsa` archive opened for reading.
+///
+/// Only the list of files is loaded when opening an archive. Their contents are read from disk
+/// when requested, so the archive must not be changed or moved while in use.
+#[derive(Clone, Debug, Default)]
+pub struct Archive {
+    path: PathBuf,
+    entries: Vec<ArchiveEntry>,
+    /// Positions in `entries` by normalized name.
+    positions: HashMap<String, usize>,
+}
+
+impl Archive {
+    pub const VERSION: u32 = 0x100;
+
+    /// The size of the header, which precedes the file records.
+    const HEADER_SIZE: usize = 12;
+
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let archive_size = file.metadata()?.len();
+
+        // validate header
+        let mut header = [0; Self::HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        let mut stream = Reader::new(&header);
+        let version: u32 = stream.load()?;
+        if version != Self::VERSION {
+            return Reader::error("Invalid BSA Version");
+        }
+        let hash_offset = stream.load::<u32>()? as usize;
+        let file_count = stream.load::<u32>()? as usize;
+
+        // the file records, name offsets and names, followed by the hash table
+        let directory_size = hash_offset + file_count * 8;
+        let data_offset = (Self::HEADER_SIZE + directory_size) as u64;
+        if data_offset > archive_size {
+            return Reader::error("Invalid BSA Directory Size");
+        }
+        let mut directory = vec![0; directory_size];
+        file.read_exact(&mut directory)?;
+
+        let mut stream = Reader::new(&directory);
+        let records: Vec<[u32; 2]> = stream.load_vec(file_count)?;
+        let name_offsets: Vec<u32> = stream.load_vec(file_count)?;
+        let Some(names) = directory.get(file_count * 12..hash_offset) else {
+            return Reader::error("Invalid BSA Hash Offset");
+        };
+        stream.cursor.set_position(hash_offset as u64);
+        let hashes: Vec<[u32; 2]> = stream.load_vec(file_count)?;
+
+        let mut entries = Vec::with_capacity(file_count);
+        for ((&[size, offset], &name_offset), &[low, high]) in records.iter().zip(&name_offsets).zip(&hashes) {
+            let Some(name) = names.get(name_offset as usize..) else {
+                return Reader::error("Invalid BSA Name Offset");
+            };
+            let name = name.split(|&byte| byte == 0).next().unwrap_or_default();
+
+            let offset = data_offset + u64::from(offset);
+            if offset + u64::from(size) > archive_size {
+                return Reader::error("Invalid BSA File Offset");
+            }
+
+            entries.push(ArchiveEntry {
+                name: WINDOWS_1252.decode_without_bom_handling(name).0.into_owned(),
+                hash: FileHash { low, high },
+                offset,
+                size: size as usize,
+            });
+        }
+
+        let positions = entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (normalize_name(&entry.name), position))
+            .collect();
+
+        Ok(Self {
+            path: path.to_owned(),
+            entries,
+            positions,
+        })
+    }
+
+    /// The path the archive was opened from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The files in the archive, in the order they are listed.
+    pub fn entries(&self) -> &[ArchiveEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The file with the given name, ignoring ascii case and the kind of slashes used.
+    pub fn get(&self, name: &str) -> Option<&ArchiveEntry> {
+        let position = self.positions.get(&normalize_name(name))?;
+        Some(&self.entries[*position])
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.positions.contains_key(&normalize_name(name))
+    }
+
+    /// Read the contents of the file with the given name, see [`Archive::get`].
+    pub fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        let Some(entry) = self.get(name) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{name} not found in {}", self.path.display()),
+            ));
+        };
+        self.read_entry(entry)
+    }
+
+    /// Read the contents of a file in this archive.
+    pub fn read_entry(&self, entry: &ArchiveEntry) -> io::Result<Vec<u8>> {
+        read_from(&mut File::open(&self.path)?, entry)
+    }
+
+    /// Extract every file into `dir`, creating subdirectories as needed.
+    ///
+    /// Fails without writing anything if a file name would escape `dir`.
+    pub fn extract(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+
+        let paths = self
+            .entries
+            .iter()
+            .map(|entry| extract_path(dir, &entry.name))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut file = File::open(&self.path)?;
+        for (entry, path) in self.entries.iter().zip(paths) {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, read_from(&mut file, entry)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load the mesh with the given name, see [`Archive::get`].
+    #[cfg(feature = "nif")]
+    pub fn load_nif(&self, name: &str) -> io::Result<nif::NiStream> {
+        nif::NiStream::from_bytes(&self.read(name)?)
+    }
+}
+
+fn read_from(file: &mut File, entry: &ArchiveEntry) -> io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(entry.offset))?;
+    let mut bytes = vec![0; entry.size];
+    file.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The path under `dir` for a file named `name`, which must be relative and not use `..`.
+fn extract_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let mut path = dir.to_owned();
+    for component in name.split(['\\', '/']) {
+        if matches!(component, "" | "." | "..") || component.contains(':') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid file name for extraction: {name}"),
+            ));
+        }
+        path.push(component);
+    }
+    Ok(path)
+}
@@ -0,0 +1,247 @@
+// rust std imports
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// external imports
+use bytes_io::Writer;
+use encoding_rs::WINDOWS_1252;
+use hashbrown::HashMap;
+
+// internal imports
+use crate::{normalize_name, Archive, FileHash};
+
+/// Creates `.bsa` archives from files held in memory.
+///
+/// ```ignore
+/// let mut builder = ArchiveBuilder::new();
+/// builder.add_dir_with_prefix("./Data Files/meshes", "meshes\\")?;
+/// builder.save_path("./Data Files/Meshes.bsa")?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveBuilder {
+    /// The name and contents of each file, by normalized name.
+    files: HashMap<String, (String, Vec<u8>)>,
+}
+
+impl ArchiveBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A builder holding every file of `archive`, for making changes to it.
+    pub fn from_archive(archive: &Archive) -> io::Result<Self> {
+        let mut this = Self::new();
+        for entry in archive.entries() {
+            this.insert(&entry.name, archive.read_entry(entry)?);
+        }
+        Ok(this)
+    }
+
+    /// Add a file, replacing any file with the same name (ignoring ascii case and the kind of
+    /// slashes used). Returns the contents of the replaced file.
+    pub fn insert(&mut self, name: &str, contents: Vec<u8>) -> Option<Vec<u8>> {
+        let name = name.replace('/', "\\").trim_start_matches('\\').to_owned();
+        let (_, old) = self.files.insert(normalize_name(&name), (name, contents))?;
+        Some(old)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Vec<u8>> {
+        let (_, contents) = self.files.remove(&normalize_name(name))?;
+        Some(contents)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.files.contains_key(&normalize_name(name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Add every file under `dir`, named by their path relative to it.
+    ///
+    /// For an archive to be used by the game, `dir` should be `Data Files` or a directory with the
+    /// same layout, so that names begin with e.g. `meshes\`.
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        self.add_files(dir.as_ref(), "")
+    }
+
+    /// Add every file under `dir`, named by their path relative to it after `prefix`.
+    ///
+    /// Used to add a single subdirectory of `Data Files`, e.g. `meshes` with the prefix `meshes\`.
+    pub fn add_dir_with_prefix(&mut self, dir: impl AsRef<Path>, prefix: &str) -> io::Result<()> {
+        self.add_files(dir.as_ref(), prefix)
+    }
+
+    fn add_files(&mut self, dir: &Path, prefix: &str) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let Some(file_name) = entry.file_name().to_str().map(|name| format!("{prefix}{name}")) else {
+                return Writer::error(format!("Invalid file name: {}", entry.path().display()));
+            };
+            if entry.file_type()?.is_dir() {
+                self.add_files(&entry.path(), &format!("{file_name}\\"))?;
+            } else {
+                self.insert(&file_name, fs::read(entry.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save_path(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.save_bytes()?)
+    }
+
+    pub fn save_bytes(&self) -> io::Result<Vec<u8>> {
+        // the game finds files with a binary search, so they must be sorted by hash
+        let mut files = vec![];
+        for (name, contents) in self.files.values() {
+            let (bytes, _, false) = WINDOWS_1252.encode(name) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid file name: {name}"),
+                ));
+            };
+            let hash = FileHash::from_bytes(&bytes.to_ascii_lowercase());
+            files.push((hash, name, bytes, contents));
+        }
+        files.sort_by_key(|(hash, ..)| *hash);
+
+        for pair in files.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("File names have the same hash: {} and {}", pair[0].1, pair[1].1),
+                ));
+            }
+        }
+
+        let names_size: usize = files.iter().map(|(_, _, bytes, _)| bytes.len() + 1).sum();
+        let hash_offset = files.len() * 12 + names_size;
+
+        let mut stream = Writer::new(vec![]);
+
+        // write header
+        stream.save(&Archive::VERSION)?;
+        stream.save_as::<usize, u32>(hash_offset)?;
+        stream.save_as::<usize, u32>(files.len())?;
+
+        // write file records
+        let mut offset = 0;
+        for (.., contents) in &files {
+            stream.save_as::<usize, u32>(contents.len())?;
+            stream.save_as::<usize, u32>(offset)?;
+            offset += contents.len();
+        }
+
+        // write name offsets
+        let mut name_offset = 0;
+        for (_, _, bytes, _) in &files {
+            stream.save_as::<usize, u32>(name_offset)?;
+            name_offset += bytes.len() + 1;
+        }
+
+        // write names
+        for (_, _, bytes, _) in &files {
+            stream.save_bytes(bytes)?;
+            stream.save(&0u8)?;
+        }
+
+        // write hash table
+        for (hash, ..) in &files {
+            stream.save(&hash.low)?;
+            stream.save(&hash.high)?;
+        }
+
+        // write file contents
+        for (.., contents) in &files {
+            stream.save_bytes(contents)?;
+        }
+
+        Ok(stream.cursor.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let mut builder = ArchiveBuilder::new();
+        builder.insert("meshes/m/misc_com_bottle_01.nif", b"bottle".to_vec());
+        builder.insert("Textures\\Tx_Bottle.dds", b"texture".to_vec());
+        builder.insert("icons\\m\\misc_com_bottle_01.tga", vec![]);
+        assert_eq!(
+            builder.insert("TEXTURES/tx_bottle.dds", b"replaced".to_vec()),
+            Some(b"texture".to_vec())
+        );
+
+        let path = dir.path().join("Test.bsa");
+        builder.save_path(&path)?;
+
+        let archive = Archive::from_path(&path)?;
+        assert_eq!(archive.len(), 3);
+        assert!(archive.entries().windows(2).all(|pair| pair[0].hash < pair[1].hash));
+        for entry in archive.entries() {
+            assert_eq!(entry.hash, FileHash::new(&entry.name));
+        }
+
+        let entry = archive.get("MESHES/M/MISC_COM_BOTTLE_01.NIF").unwrap();
+        assert_eq!(entry.name, "meshes\\m\\misc_com_bottle_01.nif");
+        assert_eq!(archive.read_entry(entry)?, b"bottle");
+        assert_eq!(archive.read("textures\\tx_bottle.dds")?, b"replaced");
+        assert_eq!(archive.read("icons/m/misc_com_bottle_01.tga")?, b"");
+        assert!(!archive.contains("meshes\\m\\misc_com_bottle_02.nif"));
+        assert_eq!(
+            archive.read("meshes\\m\\misc_com_bottle_02.nif").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        // extracting and re-packing gives the same archive
+        let extracted = dir.path().join("extracted");
+        archive.extract(&extracted)?;
+        assert_eq!(
+            fs::read(extracted.join("meshes").join("m").join("misc_com_bottle_01.nif"))?,
+            b"bottle"
+        );
+
+        let mut repacked = ArchiveBuilder::new();
+        repacked.add_dir(&extracted)?;
+        assert_eq!(repacked.save_bytes()?, fs::read(&path)?);
+        assert_eq!(ArchiveBuilder::from_archive(&archive)?.save_bytes()?, fs::read(&path)?);
+
+        // a subdirectory keeps its name as a prefix
+        let mut meshes = ArchiveBuilder::new();
+        meshes.add_dir_with_prefix(extracted.join("meshes"), "meshes\\")?;
+        assert_eq!(meshes.len(), 1);
+        assert!(meshes.contains("meshes\\m\\misc_com_bottle_01.nif"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let path = dir.path().join("Invalid.bsa");
+        fs::write(&path, [0; 12])?;
+        assert!(Archive::from_path(&path).is_err());
+
+        let mut builder = ArchiveBuilder::new();
+        builder.insert("..\\Morrowind.exe", vec![]);
+        builder.save_path(&path)?;
+        let archive = Archive::from_path(&path)?;
+        assert!(archive.extract(dir.path().join("extracted")).is_err());
+        assert!(!dir.path().join("extracted").exists());
+
+        Ok(())
+    }
+}
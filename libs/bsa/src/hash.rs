@@ -0,0 +1,95 @@
+// external imports
+use encoding_rs::WINDOWS_1252;
+
+/// The hash of a file name, by which the game finds files in an archive.
+///
+/// Archives list their files sorted by hash, comparing `low` before `high`, which is the order
+/// given by [`Ord`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FileHash {
+    pub low: u32,
+    pub high: u32,
+}
+
+impl FileHash {
+    /// The hash of `name`, which is normalized first, see [`normalize_name`].
+    pub fn new(name: &str) -> Self {
+        let name = normalize_name(name);
+        let (bytes, _, _) = WINDOWS_1252.encode(&name);
+        Self::from_bytes(&bytes)
+    }
+
+    /// The hash of a name that is already normalized and encoded.
+    pub fn from_bytes(name: &[u8]) -> Self {
+        // the game hashes signed chars, so bytes above 0x7F are sign extended
+        let extend = |byte: u8| {
+            if byte < 0x80 {
+                u32::from(byte)
+            } else {
+                u32::from(byte) | 0xFFFF_FF00
+            }
+        };
+
+        let (first, second) = name.split_at(name.len() / 2);
+
+        let mut low = 0u32;
+        let mut shift = 0u32;
+        for &byte in first {
+            low ^= extend(byte) << (shift & 0x1F);
+            shift = shift.wrapping_add(8);
+        }
+
+        let mut high = 0u32;
+        let mut shift = 0u32;
+        for &byte in second {
+            let temp = extend(byte) << (shift & 0x1F);
+            high ^= temp;
+            high = high.rotate_right(temp & 0x1F);
+            shift = shift.wrapping_add(8);
+        }
+
+        Self { low, high }
+    }
+}
+
+/// The form of a file name used for hashing and comparison: lowercase, with backslashes and no
+/// leading separator, e.g. `Meshes/x/Ex_Common.nif` becomes `meshes\x\ex_common.nif`.
+pub fn normalize_name(name: &str) -> String {
+    name.replace('/', "\\").trim_start_matches('\\').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        // values computed with the hash function used by the game
+        assert_eq!(FileHash::new(""), FileHash { low: 0, high: 0 });
+        assert_eq!(
+            FileHash::new("a"),
+            FileHash {
+                low: 0,
+                high: 0x80000030
+            }
+        );
+        assert_eq!(
+            FileHash::new("meshes\\m\\misc_com_bottle_01.nif"),
+            FileHash {
+                low: 0x76252437,
+                high: 0x4cfd5c14
+            },
+        );
+        assert_eq!(
+            FileHash::new("textures\\tx_\u{e9}t\u{e9}.dds"),
+            FileHash {
+                low: 0x071d175d,
+                high: 0x1185197f
+            },
+        );
+        assert_eq!(
+            FileHash::new("meshes\\m\\misc_com_bottle_01.nif"),
+            FileHash::new("Meshes/M/Misc_Com_Bottle_01.NIF"),
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//!
+//! Reading and writing of Morrowind's `.bsa` archives.
+//!
+//! Archives are version `0x100`, where files are found by a hash of their name. Names are
+//! relative to `Data Files`, use backslashes, and are compared ignoring ascii case.
+//!
+//! [`Vfs`] combines archives with the loose files of `Data Files`, finding files as the game does.
+//!
+
+mod archive;
+mod builder;
+mod hash;
+mod vfs;
+
+pub use archive::*;
+pub use builder::*;
+pub use hash::*;
+pub use vfs::*;
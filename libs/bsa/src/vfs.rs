@@ -0,0 +1,214 @@
+// rust std imports
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// internal imports
+use crate::{normalize_name, Archive, ArchiveEntry};
+
+/// Where the game would load a file from.
+#[derive(Clone, Copy, Debug)]
+pub enum Source<'a> {
+    Loose(&'a Path),
+    Archive(&'a Archive, &'a ArchiveEntry),
+}
+
+#[derive(Clone, Debug)]
+enum Location {
+    Loose(PathBuf),
+    /// Positions in `archives` and then in the archive's entries.
+    Archive(usize, usize),
+}
+
+/// The files under `Data Files` as the game sees them, combining loose files and archives.
+///
+/// Loose files override files in archives, and archives added later override those added before,
+/// as with the `Archive N=` entries of `Morrowind.ini`. Names are relative to `Data Files` and
+/// compared ignoring ascii case and the kind of slashes used.
+///
+/// ```ignore
+/// let vfs = Vfs::from_data_files("./Data Files", ["Morrowind.bsa", "Tribunal.bsa", "Bloodmoon.bsa"])?;
+/// let stream = NiStream::from_bytes(&vfs.open("meshes\\x\\ex_hlaalu_b_01.nif")?)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Vfs {
+    archives: Vec<Archive>,
+    /// The name and location of each file, by normalized name.
+    files: BTreeMap<String, (String, Location)>,
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The files in `data_files` and in `archives`, which are given in order of increasing
+    /// priority. Relative archive paths are relative to `data_files`.
+    pub fn from_data_files(
+        data_files: impl AsRef<Path>,
+        archives: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> io::Result<Self> {
+        let data_files = data_files.as_ref();
+        let mut this = Self::new();
+        for path in archives {
+            this.add_archive(Archive::from_path(data_files.join(path))?);
+        }
+        this.add_dir(data_files)?;
+        Ok(this)
+    }
+
+    /// Add the files of `archive`, overriding those of previously added archives but not loose
+    /// files.
+    pub fn add_archive(&mut self, archive: Archive) {
+        let index = self.archives.len();
+        for (position, entry) in archive.entries().iter().enumerate() {
+            let location = Location::Archive(index, position);
+            match self.files.get_mut(&normalize_name(&entry.name)) {
+                Some((_, Location::Loose(_))) => {}
+                Some(file) => *file = (entry.name.clone(), location),
+                None => {
+                    self.files.insert(normalize_name(&entry.name), (entry.name.clone(), location));
+                }
+            }
+        }
+        self.archives.push(archive);
+    }
+
+    /// Add every file under `dir` as a loose file, named by their path relative to it.
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        self.add_dir_with_prefix(dir.as_ref(), "")
+    }
+
+    fn add_dir_with_prefix(&mut self, dir: &Path, prefix: &str) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{prefix}{}", entry.file_name().to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                self.add_dir_with_prefix(&entry.path(), &format!("{name}\\"))?;
+            } else {
+                self.files
+                    .insert(normalize_name(&name), (name, Location::Loose(entry.path())));
+            }
+        }
+        Ok(())
+    }
+
+    /// The archives, in order of increasing priority.
+    pub fn archives(&self) -> &[Archive] {
+        &self.archives
+    }
+
+    /// The number of distinct files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.files.contains_key(&normalize_name(name))
+    }
+
+    /// Where the file with the given name would be loaded from.
+    pub fn source(&self, name: &str) -> Option<Source<'_>> {
+        let (_, location) = self.files.get(&normalize_name(name))?;
+        Some(self.source_of(location))
+    }
+
+    fn source_of<'a>(&'a self, location: &'a Location) -> Source<'a> {
+        match location {
+            Location::Loose(path) => Source::Loose(path),
+            Location::Archive(index, position) => {
+                let archive = &self.archives[*index];
+                Source::Archive(archive, &archive.entries()[*position])
+            }
+        }
+    }
+
+    /// Read the contents of the file with the given name.
+    pub fn open(&self, name: &str) -> io::Result<Vec<u8>> {
+        match self.source(name) {
+            Some(Source::Loose(path)) => fs::read(path),
+            Some(Source::Archive(archive, entry)) => archive.read_entry(entry),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, format!("{name} not found"))),
+        }
+    }
+
+    /// The name and source of every file starting with `prefix`, ordered by normalized name.
+    ///
+    /// ```ignore
+    /// let sounds: Vec<_> = vfs.list_prefix("sound/fx/").map(|(name, _)| name).collect();
+    /// ```
+    pub fn list_prefix(&self, prefix: &str) -> impl Iterator<Item = (&str, Source<'_>)> {
+        let prefix = normalize_name(prefix);
+        self.files
+            .range(prefix.clone()..)
+            .take_while(move |(key, _)| key.starts_with(&prefix))
+            .map(|(_, (name, location))| (name.as_str(), self.source_of(location)))
+    }
+
+    /// Load the mesh with the given name.
+    #[cfg(feature = "nif")]
+    pub fn load_nif(&self, name: &str) -> io::Result<nif::NiStream> {
+        nif::NiStream::from_bytes(&self.open(name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArchiveBuilder;
+
+    #[test]
+    fn priority() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let data_files = dir.path().join("Data Files");
+        fs::create_dir_all(data_files.join("Meshes").join("x"))?;
+
+        let mut builder = ArchiveBuilder::new();
+        builder.insert("meshes\\x\\ex_hlaalu_b_01.nif", b"morrowind".to_vec());
+        builder.insert("meshes\\x\\ex_hlaalu_b_02.nif", b"morrowind".to_vec());
+        builder.insert("meshes\\x\\ex_hlaalu_b_03.nif", b"morrowind".to_vec());
+        builder.insert("sound\\fx\\item\\bookpag1.wav", b"morrowind".to_vec());
+        builder.save_path(data_files.join("Morrowind.bsa"))?;
+
+        let mut builder = ArchiveBuilder::new();
+        builder.insert("Meshes\\X\\Ex_Hlaalu_B_02.NIF", b"tribunal".to_vec());
+        builder.insert("meshes\\x\\ex_hlaalu_b_03.nif", b"tribunal".to_vec());
+        builder.save_path(data_files.join("Tribunal.bsa"))?;
+
+        fs::write(data_files.join("Meshes").join("x").join("Ex_Hlaalu_B_03.nif"), b"loose")?;
+
+        let vfs = Vfs::from_data_files(&data_files, ["Morrowind.bsa", "Tribunal.bsa"])?;
+        assert_eq!(vfs.open("meshes\\x\\ex_hlaalu_b_01.nif")?, b"morrowind");
+        assert_eq!(vfs.open("meshes/x/ex_hlaalu_b_02.nif")?, b"tribunal");
+        assert_eq!(vfs.open("MESHES\\X\\EX_HLAALU_B_03.NIF")?, b"loose");
+        assert!(matches!(vfs.source("meshes\\x\\ex_hlaalu_b_03.nif"), Some(Source::Loose(_))));
+        assert!(matches!(
+            vfs.source("meshes\\x\\ex_hlaalu_b_02.nif"),
+            Some(Source::Archive(archive, _)) if archive.path().ends_with("Tribunal.bsa")
+        ));
+
+        assert!(vfs.exists("Sound/FX/item/bookpag1.wav"));
+        assert!(!vfs.exists("meshes\\x\\ex_hlaalu_b_04.nif"));
+        assert_eq!(
+            vfs.open("meshes\\x\\ex_hlaalu_b_04.nif").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        let names: Vec<_> = vfs.list_prefix("meshes/X/").map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            [
+                "meshes\\x\\ex_hlaalu_b_01.nif",
+                "Meshes\\X\\Ex_Hlaalu_B_02.NIF",
+                "Meshes\\x\\Ex_Hlaalu_B_03.nif",
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+// rust std imports
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+// external imports
+use bstr::ByteSlice;
+
+/// The kind of failure that occurred while loading.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoadErrorKind {
+    /// A record or subrecord tag that is not recognized in its context.
+    UnexpectedTag([u8; 4]),
+    /// A subrecord whose size does not match the size its contents require.
+    BadSize { expected: u32, found: u32 },
+    /// The data ended before the value being loaded was complete.
+    Truncated,
+    /// A value that is not one of those its type allows, e.g. an unknown enum variant.
+    InvalidValue { name: Cow<'static, str>, value: String },
+    /// A value that differs from the one required at this position.
+    UnexpectedValue { expected: String, found: String },
+    /// Any other failure, described by a message.
+    Other(Cow<'static, str>),
+}
+
+/// A loading failure, along with where in the file it occurred.
+///
+/// Loaders report failures as [`io::Error`] for compatibility. The structured error can be
+/// recovered from them with [`LoadError::downcast`] or `LoadError::from`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadError {
+    pub kind: LoadErrorKind,
+    /// The byte offset from the start of the file.
+    pub offset: Option<u64>,
+    /// The tag of the record being loaded, e.g. `NPC_`, or the type name of a NIF block.
+    pub record_tag: Option<String>,
+    /// The position of the record within the file.
+    pub record_index: Option<usize>,
+    /// The id of the record being loaded.
+    pub record_id: Option<String>,
+    /// The tag of the subrecord being loaded.
+    pub subrecord_tag: Option<[u8; 4]>,
+}
+
+impl LoadError {
+    pub const fn new(kind: LoadErrorKind) -> Self {
+        Self {
+            kind,
+            offset: None,
+            record_tag: None,
+            record_index: None,
+            record_id: None,
+            subrecord_tag: None,
+        }
+    }
+
+    /// The structured error carried by an [`io::Error`], if it has one.
+    pub fn downcast(error: &io::Error) -> Option<&Self> {
+        error.get_ref()?.downcast_ref()
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        let kind = match error.kind() {
+            io::ErrorKind::UnexpectedEof => LoadErrorKind::Truncated,
+            _ => LoadErrorKind::Other(error.to_string().into()),
+        };
+        let inner = error.into_inner().and_then(|inner| inner.downcast().ok());
+        inner.map_or_else(|| Self::new(kind), |this| *this)
+    }
+}
+
+impl From<LoadError> for io::Error {
+    fn from(error: LoadError) -> Self {
+        let kind = match error.kind {
+            LoadErrorKind::Truncated => io::ErrorKind::UnexpectedEof,
+            _ => io::ErrorKind::InvalidData,
+        };
+        Self::new(kind, error)
+    }
+}
+
+impl fmt::Display for LoadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedTag(tag) => write!(f, "Unexpected Tag: {}", tag.to_str_lossy()),
+            Self::BadSize { expected, found } => write!(f, "Bad Size: expected {expected}, found {found}"),
+            Self::Truncated => write!(f, "Unexpected end of data"),
+            Self::InvalidValue { name, value } => write!(f, "Invalid {name}: {value}"),
+            Self::UnexpectedValue { expected, found } => {
+                write!(f, "Unexpected Value: expected {expected}, found {found}")
+            }
+            Self::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        let mut context = vec![];
+        if let Some(tag) = &self.record_tag {
+            context.push(format!("record: {tag}"));
+        }
+        if let Some(index) = self.record_index {
+            context.push(format!("index: {index}"));
+        }
+        if let Some(id) = &self.record_id {
+            context.push(format!("id: {id:?}"));
+        }
+        if let Some(tag) = &self.subrecord_tag {
+            context.push(format!("subrecord: {}", tag.to_str_lossy()));
+        }
+        if let Some(offset) = self.offset {
+            context.push(format!("offset: {offset:#x}"));
+        }
+        if !context.is_empty() {
+            write!(f, " ({})", context.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for LoadError {}
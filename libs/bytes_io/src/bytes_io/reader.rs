@@ -1,5 +1,6 @@
 // rust std imports
 use std::borrow::Cow;
+use std::fmt::Debug;
 use std::io::{self, Read};
 
 // external imports
@@ -9,13 +10,15 @@ use memchr::memchr;
 use smart_default::SmartDefault;
 
 // internal imports
-use crate::Load;
+use crate::{Load, LoadError, LoadErrorKind};
 
 #[derive(Debug, SmartDefault)]
 pub struct Reader<'a> {
     pub cursor: io::Cursor<&'a [u8]>,
     #[default(WINDOWS_1252)]
     pub encoding: &'static Encoding,
+    /// Whether loaders should keep unrecognized data rather than fail.
+    pub lenient: bool,
 }
 
 impl<'a> Reader<'a> {
@@ -30,7 +33,14 @@ impl<'a> Reader<'a> {
     where
         M: Into<Cow<'static, str>>,
     {
-        Err(io::Error::new(io::ErrorKind::InvalidData, message.into()))
+        Err(LoadError::new(LoadErrorKind::Other(message.into())).into())
+    }
+
+    /// Fail with an error of the given kind, located at the current position.
+    pub fn fail<T>(&self, kind: LoadErrorKind) -> io::Result<T> {
+        let mut error = LoadError::new(kind);
+        error.offset = Some(self.cursor.position());
+        Err(error.into())
     }
 
     pub fn load<L>(&mut self) -> io::Result<L>
@@ -98,15 +108,12 @@ impl<'a> Reader<'a> {
             return Ok(bytes.into());
         }
 
-        Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("decode error: offset={}", self.cursor.position()),
-        ))
+        self.fail(LoadErrorKind::Other("decode error".into()))
     }
 
     pub fn expect<L>(&mut self, expected: L) -> io::Result<()>
     where
-        L: Copy + Load + PartialEq,
+        L: Copy + Debug + Load + PartialEq,
     {
         let pos = self.cursor.position();
         let value: L = self.load()?;
@@ -114,7 +121,22 @@ impl<'a> Reader<'a> {
             Ok(())
         } else {
             self.cursor.set_position(pos);
-            Self::error("Unexpected Value")
+            self.fail(LoadErrorKind::UnexpectedValue {
+                expected: format!("{expected:?}"),
+                found: format!("{value:?}"),
+            })
+        }
+    }
+
+    /// Like [`Reader::expect`], but for the size field of a subrecord.
+    pub fn expect_size(&mut self, expected: u32) -> io::Result<()> {
+        let pos = self.cursor.position();
+        let found: u32 = self.load()?;
+        if found == expected {
+            Ok(())
+        } else {
+            self.cursor.set_position(pos);
+            self.fail(LoadErrorKind::BadSize { expected, found })
         }
     }
 
@@ -125,7 +147,7 @@ impl<'a> Reader<'a> {
             self.cursor.set_position(new_pos);
             Ok(new_pos)
         } else {
-            Err(io::Error::new(io::ErrorKind::InvalidData, "Skip out of bounds"))
+            self.fail(LoadErrorKind::Truncated)
         }
     }
 }
@@ -1,7 +1,7 @@
 /// A cursed macro that generates a macro!
 ///
 macro_rules! make_delegate {
-    ($D:tt $($T:ident)*) => {
+    ($D:tt [$($T:ident)*] [$($U:ident)*]) => {
         /// A convenience macro repeating code across all `TES3Object` variants.
         ///
         /// Supports two forms:
@@ -30,6 +30,8 @@ macro_rules! make_delegate {
         /// }
         /// ```
         ///
+        /// Variants holding unrecognized records (`Unknown`) are only covered by the second form.
+        /// Traits must be implemented for them separately.
         ///
         #[macro_export]
         macro_rules! delegate {
@@ -43,6 +45,9 @@ macro_rules! make_delegate {
                     $(
                         TES3Object::$T($D name) => $D body,
                     )*
+                    $(
+                        TES3Object::$U($D name) => $D body,
+                    )*
                 }
             }
         }
@@ -50,6 +55,7 @@ macro_rules! make_delegate {
 }
 make_delegate!(
     $
+    [
     Header
     GameSetting
     GlobalVariable
@@ -93,4 +99,8 @@ make_delegate!(
     SoundGen
     Dialogue
     DialogueInfo
+    ]
+    [
+    Unknown
+    ]
 );
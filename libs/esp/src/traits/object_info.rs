@@ -3,6 +3,8 @@ use crate::prelude::*;
 pub trait ObjectInfo {
     fn object_flags(&self) -> &ObjectFlags;
     fn object_flags_mut(&mut self) -> &mut ObjectFlags;
+    fn original_bytes(&self) -> &OriginalBytes;
+    fn original_bytes_mut(&mut self) -> &mut OriginalBytes;
 
     #[inline(always)]
     fn modified(&self) -> bool {
@@ -65,6 +67,29 @@ delegate! {
         fn object_flags_mut(&mut self) -> &mut ObjectFlags {
             &mut self.flags
         }
+        #[inline(always)]
+        fn original_bytes(&self) -> &OriginalBytes {
+            &self.original_bytes
+        }
+        #[inline(always)]
+        fn original_bytes_mut(&mut self) -> &mut OriginalBytes {
+            &mut self.original_bytes
+        }
+    }
+}
+
+impl ObjectInfo for Unknown {
+    fn object_flags(&self) -> &ObjectFlags {
+        &self.flags
+    }
+    fn object_flags_mut(&mut self) -> &mut ObjectFlags {
+        &mut self.flags
+    }
+    fn original_bytes(&self) -> &OriginalBytes {
+        &self.original_bytes
+    }
+    fn original_bytes_mut(&mut self) -> &mut OriginalBytes {
+        &mut self.original_bytes
     }
 }
 
@@ -83,4 +108,18 @@ impl ObjectInfo for TES3Object {
             }
         }
     }
+    fn original_bytes(&self) -> &OriginalBytes {
+        delegate! {
+            match self {
+                inner => inner.original_bytes()
+            }
+        }
+    }
+    fn original_bytes_mut(&mut self) -> &mut OriginalBytes {
+        delegate! {
+            match self {
+                inner => inner.original_bytes_mut()
+            }
+        }
+    }
 }
@@ -1,3 +1,7 @@
+// rust std imports
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+// internal imports
 use crate::prelude::*;
 
 pub trait TypeInfo {
@@ -6,6 +10,11 @@ pub trait TypeInfo {
     fn type_name(&self) -> &'static str;
 }
 
+/// Record types whose tag is known statically, i.e. every [`TES3Object`] variant except `Unknown`.
+pub trait RecordType {
+    const TAG: &'static [u8; 4];
+}
+
 delegate! {
     impl TypeInfo {
         #[inline(always)]
@@ -23,6 +32,33 @@ delegate! {
     }
 }
 
+delegate! {
+    impl RecordType {
+        const TAG: &'static [u8; 4] = Self::TAG;
+    }
+}
+
+/// Unknown records return the tag they were loaded with. Tags only known at runtime are interned,
+/// so that each distinct tag is allocated once and lives for the rest of the program.
+impl TypeInfo for Unknown {
+    fn tag(&self) -> &'static [u8; 4] {
+        static TAGS: LazyLock<Mutex<HashSet<&'static [u8; 4]>>> = LazyLock::new(default);
+        let mut tags = TAGS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(tag) = tags.get(&self.tag) {
+            return tag;
+        }
+        let tag = Box::leak(Box::new(self.tag));
+        tags.insert(tag);
+        tag
+    }
+    fn tag_str(&self) -> &'static str {
+        std::str::from_utf8(self.tag()).unwrap_or("????")
+    }
+    fn type_name(&self) -> &'static str {
+        "Unknown"
+    }
+}
+
 impl TypeInfo for TES3Object {
     fn tag(&self) -> &'static [u8; 4] {
         delegate! {
@@ -36,6 +36,7 @@ mod lockpick;
 mod magiceffect;
 mod miscitem;
 mod npc;
+mod original_bytes;
 mod pathgrid;
 mod plugin;
 mod probe;
@@ -51,6 +52,7 @@ mod spell;
 mod startscript;
 mod static_;
 mod string;
+mod unknown;
 mod weapon;
 
 pub use activator::*;
@@ -89,6 +91,7 @@ pub use lockpick::*;
 pub use magiceffect::*;
 pub use miscitem::*;
 pub use npc::*;
+pub use original_bytes::*;
 pub use pathgrid::*;
 pub use plugin::*;
 pub use probe::*;
@@ -104,6 +107,7 @@ pub use spell::*;
 pub use startscript::*;
 pub use static_::*;
 pub use string::*;
+pub use unknown::*;
 pub use weapon::*;
 
 #[rustfmt::skip]
@@ -153,4 +157,5 @@ pub enum TES3Object {
     #[tag("PGRD")] PathGrid(PathGrid),
     #[tag("DIAL")] Dialogue(Dialogue),
     #[tag("INFO")] DialogueInfo(DialogueInfo),
+    Unknown(Unknown),
 }
@@ -9,6 +9,10 @@ pub struct Activator {
     pub name: String,
     pub script: String,
     pub mesh: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for Activator {
@@ -37,7 +41,8 @@ impl Load for Activator {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -73,6 +78,10 @@ impl Save for Activator {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
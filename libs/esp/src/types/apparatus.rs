@@ -11,6 +11,10 @@ pub struct Apparatus {
     pub mesh: String,
     pub icon: String,
     pub data: ApparatusData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -43,7 +47,7 @@ impl Load for Apparatus {
                     this.script = stream.load()?;
                 }
                 b"AADT" => {
-                    stream.expect(16u32)?;
+                    stream.expect_size(16)?;
                     this.data = stream.load()?;
                 }
                 b"ITEX" => {
@@ -55,7 +59,8 @@ impl Load for Apparatus {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -100,6 +105,10 @@ impl Save for Apparatus {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -13,6 +13,10 @@ pub struct Armor {
     pub enchanting: String,
     pub biped_objects: Vec<BipedObject>,
     pub data: ArmorData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -47,7 +51,7 @@ impl Load for Armor {
                     this.script = stream.load()?;
                 }
                 b"AODT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.data = stream.load()?;
                 }
                 b"ITEX" => {
@@ -65,7 +69,8 @@ impl Load for Armor {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -119,6 +124,10 @@ impl Save for Armor {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
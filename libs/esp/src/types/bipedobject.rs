@@ -13,7 +13,7 @@ impl Load for BipedObject {
     fn load(stream: &mut Reader<'_>) -> io::Result<Self> {
         let mut this: Self = default();
         // INDX
-        stream.expect(1u32)?;
+        stream.expect_size(1)?;
         this.biped_object_type = stream.load()?;
         //
         for _ in 0..2 {
@@ -10,6 +10,10 @@ pub struct Birthsign {
     pub texture: String,
     pub description: String,
     pub spells: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for Birthsign {
@@ -41,7 +45,8 @@ impl Load for Birthsign {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -83,6 +88,10 @@ impl Save for Birthsign {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
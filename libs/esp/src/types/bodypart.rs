@@ -9,6 +9,10 @@ pub struct Bodypart {
     pub race: String,
     pub mesh: String,
     pub data: BodypartData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -38,7 +42,7 @@ impl Load for Bodypart {
                     this.race = stream.load()?;
                 }
                 b"BYDT" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.data = stream.load()?;
                 }
                 b"DELE" => {
@@ -47,7 +51,8 @@ impl Load for Bodypart {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -82,6 +87,10 @@ impl Save for Bodypart {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
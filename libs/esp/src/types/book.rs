@@ -13,6 +13,10 @@ pub struct Book {
     pub enchanting: String,
     pub text: String,
     pub data: BookData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -43,7 +47,7 @@ impl Load for Book {
                     this.name = stream.load()?;
                 }
                 b"BKDT" => {
-                    stream.expect(20u32)?;
+                    stream.expect_size(20)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -64,7 +68,8 @@ impl Load for Book {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -119,6 +124,10 @@ impl Save for Book {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
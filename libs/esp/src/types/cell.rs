@@ -13,6 +13,10 @@ pub struct Cell {
     pub atmosphere_data: Option<AtmosphereData>,
     #[cfg_attr(feature = "serde", serde(with = "crate::features::serde::cell_references"))]
     pub references: HashMap<(u32, u32), Reference>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -46,18 +50,18 @@ impl Load for Cell {
                     this.name = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.data = stream.load()?;
                 }
                 b"RGNN" => {
                     this.region = Some(stream.load()?);
                 }
                 b"NAM5" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.map_color = Some(stream.load()?);
                 }
                 b"WHGT" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.water_height = Some(stream.load()?);
                 }
                 b"AMBI" => {
@@ -68,16 +72,16 @@ impl Load for Cell {
                     stream.skip(size - 16)?;
                 }
                 b"NAM0" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     num_temp_refs = stream.load()?;
                 }
                 b"MVRF" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let packed_indices = stream.load()?;
                     let indices = unpack(packed_indices);
                     // "MVRF" is always followed by "CNDT"
                     stream.expect(*b"CNDT")?;
-                    stream.expect(8u32)?;
+                    stream.expect_size(8)?;
                     let moved_cell = stream.load()?;
                     // MVRF/CNDT are independent of other subrecords
                     // the moved reference may not have been loaded yet at this point
@@ -85,7 +89,7 @@ impl Load for Cell {
                     moved_refs.push((indices, moved_cell));
                 }
                 b"FRMR" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let packed_indices = stream.load()?;
                     let indices = unpack(packed_indices);
                     // unpack indices
@@ -105,7 +109,7 @@ impl Load for Cell {
                     }
                 }
                 b"INTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let water_height: i32 = stream.load()?;
                     #[allow(clippy::cast_precision_loss)]
                     {
@@ -119,7 +123,8 @@ impl Load for Cell {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -184,6 +189,10 @@ impl Save for Cell {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         //
         let mut num_temp_refs = 0;
         for (i, (key, reference)) in self.references_sorted().into_iter().enumerate() {
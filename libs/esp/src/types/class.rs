@@ -9,6 +9,10 @@ pub struct Class {
     pub name: String,
     pub description: String,
     pub data: ClassData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -46,7 +50,7 @@ impl Load for Class {
                     this.name = stream.load()?;
                 }
                 b"CLDT" => {
-                    stream.expect(60u32)?;
+                    stream.expect_size(60)?;
                     this.data = stream.load()?;
                 }
                 b"DESC" => {
@@ -58,7 +62,8 @@ impl Load for Class {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -93,6 +98,10 @@ impl Save for Class {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
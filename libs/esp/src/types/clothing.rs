@@ -13,6 +13,10 @@ pub struct Clothing {
     pub enchanting: String,
     pub biped_objects: Vec<BipedObject>,
     pub data: ClothingData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -42,7 +46,7 @@ impl Load for Clothing {
                     this.name = stream.load()?;
                 }
                 b"CTDT" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -63,7 +67,8 @@ impl Load for Clothing {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -117,6 +122,10 @@ impl Save for Clothing {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
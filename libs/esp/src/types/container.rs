@@ -12,6 +12,10 @@ pub struct Container {
     pub encumbrance: f32,
     pub container_flags: ContainerFlags,
     pub inventory: Vec<(i32, FixedString<32>)>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for Container {
@@ -32,18 +36,18 @@ impl Load for Container {
                     this.name = stream.load()?;
                 }
                 b"CNDT" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.encumbrance = stream.load()?;
                 }
                 b"FLAG" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.container_flags = stream.load()?;
                 }
                 b"SCRI" => {
                     this.script = stream.load()?;
                 }
                 b"NPCO" => {
-                    stream.expect(36u32)?;
+                    stream.expect_size(36)?;
                     this.inventory.push(stream.load()?);
                 }
                 b"DELE" => {
@@ -52,7 +56,8 @@ impl Load for Container {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -103,6 +108,10 @@ impl Save for Container {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -19,6 +19,10 @@ pub struct Creature {
     pub creature_flags: CreatureFlags,
     pub blood_type: u8,
     pub data: CreatureData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -71,51 +75,51 @@ impl Load for Creature {
                     this.script = stream.load()?;
                 }
                 b"NPDT" => {
-                    stream.expect(96u32)?;
+                    stream.expect_size(96)?;
                     this.data = stream.load()?;
                 }
                 b"FLAG" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let flags = stream.load()?;
                     (this.creature_flags, this.blood_type) = unpack_flags(flags);
                 }
                 b"XSCL" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.scale = Some(stream.load()?);
                 }
                 b"NPCO" => {
-                    stream.expect(36u32)?;
+                    stream.expect_size(36)?;
                     this.inventory.push(stream.load()?);
                 }
                 b"NPCS" => {
                     this.spells.push(stream.load()?);
                 }
                 b"AIDT" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.ai_data = stream.load()?;
                 }
                 b"DODT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.travel_destinations.push(stream.load()?);
                 }
                 b"AI_T" => {
-                    stream.expect(16u32)?;
+                    stream.expect_size(16)?;
                     this.ai_packages.push(AiPackage::Travel(stream.load()?));
                 }
                 b"AI_W" => {
-                    stream.expect(14u32)?;
+                    stream.expect_size(14)?;
                     this.ai_packages.push(AiPackage::Wander(stream.load()?));
                 }
                 b"AI_E" => {
-                    stream.expect(48u32)?;
+                    stream.expect_size(48)?;
                     this.ai_packages.push(AiPackage::Escort(stream.load()?));
                 }
                 b"AI_F" => {
-                    stream.expect(48u32)?;
+                    stream.expect_size(48)?;
                     this.ai_packages.push(AiPackage::Follow(stream.load()?));
                 }
                 b"AI_A" => {
-                    stream.expect(33u32)?;
+                    stream.expect_size(33)?;
                     this.ai_packages.push(AiPackage::Activate(stream.load()?));
                 }
                 b"DELE" => {
@@ -124,7 +128,8 @@ impl Load for Creature {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -240,6 +245,10 @@ impl Save for Creature {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
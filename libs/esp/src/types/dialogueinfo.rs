@@ -20,6 +20,10 @@ pub struct DialogueInfo {
     pub quest_state: Option<QuestState>,
     pub filters: Vec<Filter>,
     pub script_text: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -78,7 +82,7 @@ impl Load for DialogueInfo {
                     this.next_id = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.data = stream.load()?;
                 }
                 b"ONAM" => {
@@ -124,12 +128,12 @@ impl Load for DialogueInfo {
                     this.filters.push(stream.load()?);
                 }
                 b"FLTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let filter = this.filters.last_mut().ok_or_else(err)?;
                     filter.value = FilterValue::Float(stream.load()?);
                 }
                 b"INTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let filter = this.filters.last_mut().ok_or_else(err)?;
                     filter.value = FilterValue::Integer(stream.load()?);
                 }
@@ -142,7 +146,8 @@ impl Load for DialogueInfo {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -262,6 +267,10 @@ impl Save for DialogueInfo {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -307,7 +316,10 @@ impl Load for Filter {
         let value = default();
         // Convert the index from a char to a number for convenience.
         let Some(index) = index.checked_sub(b'0') else {
-            return Reader::error("DialogueInfo: Invalid filter index");
+            return stream.fail(LoadErrorKind::InvalidValue {
+                name: "DialogueInfo filter index".into(),
+                value: index.to_string(),
+            });
         };
         Ok(Self {
             index,
@@ -8,6 +8,10 @@ pub struct Enchanting {
     pub id: String,
     pub effects: Vec<Effect>,
     pub data: EnchantingData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -31,11 +35,11 @@ impl Load for Enchanting {
                     this.id = stream.load()?;
                 }
                 b"ENDT" => {
-                    stream.expect(16u32)?;
+                    stream.expect_size(16)?;
                     this.data = stream.load()?;
                 }
                 b"ENAM" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.effects.push(stream.load()?);
                 }
                 b"DELE" => {
@@ -44,7 +48,8 @@ impl Load for Enchanting {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -75,6 +80,10 @@ impl Save for Enchanting {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -10,6 +10,10 @@ pub struct Faction {
     pub rank_names: Vec<String>,
     pub reactions: Vec<FactionReaction>,
     pub data: FactionData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -55,7 +59,7 @@ impl Load for Faction {
                     this.rank_names.push(stream.load()?);
                 }
                 b"FADT" => {
-                    stream.expect(240u32)?;
+                    stream.expect_size(240)?;
                     this.data = stream.load()?;
                 }
                 b"ANAM" => {
@@ -67,7 +71,8 @@ impl Load for Faction {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -107,6 +112,10 @@ impl Save for Faction {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -155,7 +164,7 @@ impl Load for FactionReaction {
         let faction = stream.load()?;
         // INTV
         stream.expect(*b"INTV")?;
-        stream.expect(4u32)?;
+        stream.expect_size(4)?;
         let reaction = stream.load()?;
         Ok(Self { faction, reaction })
     }
@@ -7,6 +7,10 @@ pub struct GameSetting {
     pub flags: ObjectFlags,
     pub id: String,
     pub value: GameSettingValue,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -33,15 +37,16 @@ impl Load for GameSetting {
                     this.value = GameSettingValue::String(stream.load()?);
                 }
                 b"FLTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.value = GameSettingValue::Float(stream.load()?);
                 }
                 b"INTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.value = GameSettingValue::Integer(stream.load()?);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -76,6 +81,10 @@ impl Save for GameSetting {
                 stream.save(value)?;
             }
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -7,6 +7,10 @@ pub struct GlobalVariable {
     pub flags: ObjectFlags,
     pub id: String,
     pub value: GlobalValue,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -30,10 +34,10 @@ impl Load for GlobalVariable {
                     this.id = stream.load()?;
                 }
                 b"FNAM" => {
-                    stream.expect(1u32)?;
+                    stream.expect_size(1)?;
                     let global_type = stream.load()?;
                     stream.expect(*b"FLTV")?;
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let global_value = stream.load()?;
                     this.value = GlobalValue::from_f32(global_type, global_value);
                 }
@@ -43,7 +47,8 @@ impl Load for GlobalVariable {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -76,6 +81,10 @@ impl Save for GlobalVariable {
         if self.value.has_precision_error() {
             Reader::error(format!("GlobalVariable precision error: {}", self.id))?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -129,4 +138,27 @@ mod tests {
         let value = GlobalValue::Long(16777217);
         assert!(value.has_precision_error());
     }
+
+    #[test]
+    fn test_load_error_context() {
+        let mut record = vec![];
+        for (tag, data) in [(b"NAME", &b"gold\0"[..]), (b"FNAM", b"l"), (b"FLTV", b"12")] {
+            record.extend(tag);
+            record.extend(u32::try_from(data.len()).unwrap().to_le_bytes());
+            record.extend(data);
+        }
+        let mut bytes = b"GLOB".to_vec();
+        bytes.extend(u32::try_from(record.len()).unwrap().to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes.extend(record);
+
+        let error = Plugin::new().load_bytes(&bytes).unwrap_err();
+        let error = LoadError::from(error);
+        assert_eq!(error.kind, LoadErrorKind::BadSize { expected: 4, found: 2 });
+        assert_eq!(error.record_tag.as_deref(), Some("GLOB"));
+        assert_eq!(error.record_index, Some(0));
+        assert_eq!(error.record_id.as_deref(), Some("gold"));
+        assert_eq!(error.subrecord_tag, Some(*b"FLTV"));
+        assert_eq!(error.offset, Some(42));
+    }
 }
@@ -16,6 +16,10 @@ pub struct Header {
     pub description: FixedString<256>,
     pub num_objects: u32,
     pub masters: Vec<(String, u64)>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for Header {
@@ -27,7 +31,7 @@ impl Load for Header {
         while let Ok(tag) = stream.load() {
             match &tag {
                 b"HEDR" => {
-                    stream.expect(300u32)?;
+                    stream.expect_size(300)?;
                     this.version = stream.load()?;
                     this.file_type = stream.load()?;
                     this.author = stream.load()?;
@@ -38,13 +42,14 @@ impl Load for Header {
                     let master_name = stream.load()?;
                     // DATA
                     stream.expect(*b"DATA")?;
-                    stream.expect(8u32)?;
+                    stream.expect_size(8)?;
                     let master_size = stream.load()?;
                     //
                     this.masters.push((master_name, master_size));
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -74,6 +79,10 @@ impl Save for Header {
             stream.save(&8u32)?;
             stream.save(master_size)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -11,6 +11,10 @@ pub struct Ingredient {
     pub mesh: String,
     pub icon: String,
     pub data: IngredientData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -41,7 +45,7 @@ impl Load for Ingredient {
                     this.name = stream.load()?;
                 }
                 b"IRDT" => {
-                    stream.expect(56u32)?;
+                    stream.expect_size(56)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -56,7 +60,8 @@ impl Load for Ingredient {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -101,6 +106,10 @@ impl Save for Ingredient {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -16,6 +16,10 @@ pub struct Landscape {
     pub world_map_data: WorldMapData,
     pub vertex_colors: VertexColors,
     pub texture_indices: TextureIndices,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -63,31 +67,31 @@ impl Load for Landscape {
         while let Ok(tag) = stream.load() {
             match &tag {
                 b"INTV" => {
-                    stream.expect(8u32)?;
+                    stream.expect_size(8)?;
                     this.grid = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.landscape_flags = stream.load()?;
                 }
                 b"VNML" => {
-                    stream.expect(12675u32)?;
+                    stream.expect_size(12675)?;
                     this.vertex_normals = stream.load()?;
                 }
                 b"VHGT" => {
-                    stream.expect(4232u32)?;
+                    stream.expect_size(4232)?;
                     this.vertex_heights = stream.load()?;
                 }
                 b"WNAM" => {
-                    stream.expect(81u32)?;
+                    stream.expect_size(81)?;
                     this.world_map_data = stream.load()?;
                 }
                 b"VCLR" => {
-                    stream.expect(12675u32)?;
+                    stream.expect_size(12675)?;
                     this.vertex_colors = stream.load()?;
                 }
                 b"VTEX" => {
-                    stream.expect(512u32)?;
+                    stream.expect_size(512)?;
                     this.texture_indices = stream.load()?;
                 }
                 b"DELE" => {
@@ -96,7 +100,8 @@ impl Load for Landscape {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -154,6 +159,10 @@ impl Save for Landscape {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -230,6 +239,29 @@ impl Landscape {
         heights
     }
 
+    /// Texture indices in row-major order.
+    ///
+    /// The `VTEX` subrecord stores textures in 4x4 blocks of 4x4 tiles.
+    pub fn decode_texture_indices(&self) -> Box<[[u16; 16]; 16]> {
+        let data = self.texture_indices.data.as_flattened();
+
+        let mut indices: Box<[[u16; 16]; 16]> = zeroed_box();
+
+        let mut i = 0;
+        for y1 in 0..4 {
+            for x1 in 0..4 {
+                for y2 in 0..4 {
+                    for x2 in 0..4 {
+                        indices[y1 * 4 + y2][x1 * 4 + x2] = data[i];
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        indices
+    }
+
     #[allow(clippy::cast_precision_loss)]
     pub fn calculate_world_vertices(&self) -> Vec<Vec3> {
         const CELL_SIZE: f32 = 8192.0;
@@ -265,6 +297,32 @@ impl Landscape {
         vertices
     }
 
+    /// Recompute `world_map_data` from the current vertex heights.
+    ///
+    /// Matches the Construction Set: every 8th vertex is sampled and its height
+    /// is divided by 128, then clamped to the range of an `i8`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn calculate_world_map_data(&self) -> WorldMapData {
+        let heights = self.decode_vertex_heights();
+
+        let mut world_map_data = WorldMapData::default();
+
+        for (y, row) in world_map_data.data.iter_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                let height = heights[y * 8][x * 8] / 128.0;
+                *value = height.round().clamp(-128.0, 127.0) as i8;
+            }
+        }
+
+        world_map_data
+    }
+
+    /// Update `world_map_data` and `landscape_flags` to reflect the current vertex heights.
+    pub fn update_world_map_data(&mut self) {
+        self.world_map_data = self.calculate_world_map_data();
+        self.landscape_flags.insert(LandscapeFlags::USES_VERTEX_HEIGHTS_AND_NORMALS);
+    }
+
     #[allow(clippy::many_single_char_names)]
     pub fn calcuate_triangles(&self) -> Vec<[u16; 3]> {
         let mut triangles = vec![[0; 3]; 8192];
@@ -291,3 +349,69 @@ impl Landscape {
         triangles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_map_data() {
+        // heights rise by 8 units per vertex along x and 128 units per vertex along y
+        let mut landscape = Landscape::default();
+        for (y, row) in landscape.vertex_heights.data.iter_mut().enumerate() {
+            row[0] = if y == 0 { 0 } else { 16 };
+            row[1..].fill(1);
+        }
+        assert!(!landscape.landscape_flags.uses_world_map_data());
+
+        landscape.update_world_map_data();
+        assert!(landscape
+            .landscape_flags
+            .contains(LandscapeFlags::USES_VERTEX_HEIGHTS_AND_NORMALS));
+        assert!(landscape.landscape_flags.uses_world_map_data());
+
+        // every 8th vertex is sampled, then divided by 128 and rounded
+        let data = &landscape.world_map_data.data;
+        assert_eq!(data[0][..3], [0, 1, 1]);
+        assert_eq!(data[1][..3], [8, 9, 9]);
+        assert_eq!(data[8][8], 68);
+
+        // heights outside the range of an i8 are clamped
+        landscape
+            .vertex_heights
+            .data
+            .iter_mut()
+            .flatten()
+            .for_each(|delta| *delta = 0);
+        landscape.vertex_heights.offset = 2100.0;
+        assert!(landscape
+            .calculate_world_map_data()
+            .data
+            .iter()
+            .flatten()
+            .all(|&value| value == 127));
+        landscape.vertex_heights.offset = -2100.0;
+        assert!(landscape
+            .calculate_world_map_data()
+            .data
+            .iter()
+            .flatten()
+            .all(|&value| value == -128));
+    }
+
+    #[test]
+    fn test_decode_texture_indices() {
+        let mut landscape = Landscape::default();
+        for (value, index) in landscape.texture_indices.data.as_flattened_mut().iter_mut().zip(0..) {
+            *value = index;
+        }
+
+        // the 16x16 tiles are stored as 4x4 blocks of 4x4 tiles
+        let indices = landscape.decode_texture_indices();
+        assert_eq!(indices[0][..5], [0, 1, 2, 3, 16]);
+        assert_eq!(indices[1][..5], [4, 5, 6, 7, 20]);
+        assert_eq!(indices[4][0], 64);
+        assert_eq!(indices[5][6], 86);
+        assert_eq!(indices[15][15], 255);
+    }
+}
@@ -8,6 +8,10 @@ pub struct LandscapeTexture {
     pub id: String,
     pub index: u32,
     pub file_name: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for LandscapeTexture {
@@ -22,7 +26,7 @@ impl Load for LandscapeTexture {
                     this.id = stream.load()?;
                 }
                 b"INTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.index = stream.load()?;
                 }
                 b"DATA" => {
@@ -34,7 +38,8 @@ impl Load for LandscapeTexture {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -64,6 +69,10 @@ impl Save for LandscapeTexture {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -9,6 +9,10 @@ pub struct LeveledCreature {
     pub leveled_creature_flags: LeveledCreatureFlags,
     pub chance_none: u8,
     pub creatures: Vec<(String, u16)>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for LeveledCreature {
@@ -23,15 +27,15 @@ impl Load for LeveledCreature {
                     this.id = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.leveled_creature_flags = stream.load()?;
                 }
                 b"NNAM" => {
-                    stream.expect(1u32)?;
+                    stream.expect_size(1)?;
                     this.chance_none = stream.load()?;
                 }
                 b"INDX" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.creatures.reserve(stream.load_as::<u32, usize>()?);
                 }
                 b"CNAM" => {
@@ -39,7 +43,7 @@ impl Load for LeveledCreature {
                     this.creatures.last_mut().ok_or_else(err)?.0 = stream.load()?;
                 }
                 b"INTV" => {
-                    stream.expect(2u32)?;
+                    stream.expect_size(2)?;
                     this.creatures.last_mut().ok_or_else(err)?.1 = stream.load()?;
                 }
                 b"DELE" => {
@@ -48,7 +52,8 @@ impl Load for LeveledCreature {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -93,6 +98,10 @@ impl Save for LeveledCreature {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
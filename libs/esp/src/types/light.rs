@@ -12,6 +12,10 @@ pub struct Light {
     pub icon: String,
     pub sound: String,
     pub data: LightData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -46,7 +50,7 @@ impl Load for Light {
                     this.icon = stream.load()?;
                 }
                 b"LHDT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -61,7 +65,8 @@ impl Load for Light {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -111,6 +116,10 @@ impl Save for Light {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -11,6 +11,10 @@ pub struct Lockpick {
     pub mesh: String,
     pub icon: String,
     pub data: LockpickData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -40,7 +44,7 @@ impl Load for Lockpick {
                     this.name = stream.load()?;
                 }
                 b"LKDT" => {
-                    stream.expect(16u32)?;
+                    stream.expect_size(16)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -55,7 +59,8 @@ impl Load for Lockpick {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -100,6 +105,10 @@ impl Save for Lockpick {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
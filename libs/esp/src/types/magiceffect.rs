@@ -18,6 +18,10 @@ pub struct MagicEffect {
     pub area_visual: String,
     pub description: String,
     pub data: MagicEffectData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -41,11 +45,11 @@ impl Load for MagicEffect {
         while let Ok(tag) = stream.load() {
             match &tag {
                 b"INDX" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.effect_id = stream.load()?;
                 }
                 b"MEDT" => {
-                    stream.expect(36u32)?;
+                    stream.expect_size(36)?;
                     this.data = stream.load()?;
                 }
                 b"ITEX" => {
@@ -87,7 +91,8 @@ impl Load for MagicEffect {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -168,6 +173,10 @@ impl Save for MagicEffect {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
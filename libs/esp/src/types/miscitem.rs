@@ -11,6 +11,10 @@ pub struct MiscItem {
     pub mesh: String,
     pub icon: String,
     pub data: MiscItemData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -39,7 +43,7 @@ impl Load for MiscItem {
                     this.name = stream.load()?;
                 }
                 b"MCDT" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -54,7 +58,8 @@ impl Load for MiscItem {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -99,6 +104,10 @@ impl Save for MiscItem {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -22,6 +22,10 @@ pub struct Npc {
     pub npc_flags: NpcFlags,
     pub blood_type: u8,
     pub data: NpcData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -84,43 +88,43 @@ impl Load for Npc {
                     this.data = stream.load()?;
                 }
                 b"FLAG" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     let flags = stream.load()?;
                     (this.npc_flags, this.blood_type) = unpack_flags(flags);
                 }
                 b"NPCO" => {
-                    stream.expect(36u32)?;
+                    stream.expect_size(36)?;
                     this.inventory.push(stream.load()?);
                 }
                 b"NPCS" => {
                     this.spells.push(stream.load()?);
                 }
                 b"AIDT" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.ai_data = stream.load()?;
                 }
                 b"DODT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.travel_destinations.push(stream.load()?);
                 }
                 b"AI_T" => {
-                    stream.expect(16u32)?;
+                    stream.expect_size(16)?;
                     this.ai_packages.push(AiPackage::Travel(stream.load()?));
                 }
                 b"AI_W" => {
-                    stream.expect(14u32)?;
+                    stream.expect_size(14)?;
                     this.ai_packages.push(AiPackage::Wander(stream.load()?));
                 }
                 b"AI_E" => {
-                    stream.expect(48u32)?;
+                    stream.expect_size(48)?;
                     this.ai_packages.push(AiPackage::Escort(stream.load()?));
                 }
                 b"AI_F" => {
-                    stream.expect(48u32)?;
+                    stream.expect_size(48)?;
                     this.ai_packages.push(AiPackage::Follow(stream.load()?));
                 }
                 b"AI_A" => {
-                    stream.expect(33u32)?;
+                    stream.expect_size(33)?;
                     this.ai_packages.push(AiPackage::Activate(stream.load()?));
                 }
                 b"DELE" => {
@@ -129,7 +133,8 @@ impl Load for Npc {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -254,6 +259,10 @@ impl Save for Npc {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -262,6 +271,7 @@ impl Load for NpcData {
     fn load(stream: &mut Reader<'_>) -> io::Result<Self> {
         let mut this: Self = default();
 
+        let position = stream.cursor.position();
         let len: u32 = stream.load()?;
         match len {
             52 => {
@@ -282,7 +292,14 @@ impl Load for NpcData {
                 stream.skip(3)?; // padding
                 this.gold = stream.load()?;
             }
-            _ => Reader::error(format!("Unexpected length ({len}) for NPC_::NPDT"))?,
+            _ => {
+                // only the full size is reported, auto-calculated NPCs use the 12 byte form
+                stream.cursor.set_position(position);
+                stream.fail(LoadErrorKind::BadSize {
+                    expected: 52,
+                    found: len,
+                })?;
+            }
         }
 
         Ok(this)
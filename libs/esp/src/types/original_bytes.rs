@@ -0,0 +1,129 @@
+// rust std imports
+use std::fmt;
+
+// external imports
+use encoding_rs::Encoding;
+
+// internal imports
+use crate::prelude::*;
+
+/// The bytes a record was loaded from, kept so that it can be saved back unchanged.
+///
+/// Only recorded when loading with [`LoadOptions::keep_original_bytes`]. When saving, the bytes
+/// are loaded again and compared with the record, field by field. A record that still equals
+/// what was loaded is written with its original bytes, so any change to the record, even one
+/// that would not change its normal output, makes it save normally.
+///
+/// Always compares equal, so it never affects comparisons between records.
+///
+/// [`LoadOptions::keep_original_bytes`]: crate::types::LoadOptions::keep_original_bytes
+#[derive(Clone, Default)]
+pub struct OriginalBytes(Option<Box<Original>>);
+
+#[derive(Clone)]
+struct Original {
+    bytes: Box<[u8]>,
+    /// The settings of the reader the bytes were loaded with.
+    encoding: &'static Encoding,
+    lenient: bool,
+}
+
+impl OriginalBytes {
+    /// Remember the bytes `stream` was loaded from, along with its encoding and leniency.
+    pub fn new(stream: &Reader<'_>) -> Self {
+        Self(Some(Box::new(Original {
+            bytes: (*stream.cursor.get_ref()).into(),
+            encoding: stream.encoding,
+            lenient: stream.lenient,
+        })))
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn bytes(&self) -> Option<&[u8]> {
+        self.0.as_ref().map(|original| &*original.bytes)
+    }
+
+    /// Forget the original bytes, forcing the record to be saved normally.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// The original bytes, if `object` still equals the record they load as and would be saved
+    /// with the same `encoding`.
+    pub fn matching(&self, object: &TES3Object, encoding: &'static Encoding) -> Option<&[u8]> {
+        let original = self.0.as_ref()?;
+        if original.encoding != encoding {
+            return None;
+        }
+        let mut stream = Reader {
+            encoding: original.encoding,
+            lenient: original.lenient,
+            ..Reader::new(&original.bytes)
+        };
+        let loaded: TES3Object = stream.load().ok()?;
+        (loaded == *object).then_some(&*original.bytes)
+    }
+}
+
+impl PartialEq for OriginalBytes {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for OriginalBytes {}
+
+impl fmt::Debug for OriginalBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.bytes() {
+            Some(bytes) => write!(f, "OriginalBytes({} bytes)", bytes.len()),
+            None => write!(f, "OriginalBytes(None)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        let mut plugin = Plugin {
+            objects: vec![Creature {
+                id: "rat".into(),
+                scale: Some(1.5),
+                ..default()
+            }
+            .into()],
+            ..default()
+        };
+        // a scale above the maximum, which is clamped to 2.0 on save
+        let mut bytes = plugin.save_bytes().unwrap();
+        let position = bytes.find(b"XSCL").unwrap() + 8;
+        bytes[position..position + 4].copy_from_slice(&3.0f32.to_le_bytes());
+
+        let options = LoadOptions {
+            keep_original_bytes: true,
+            ..default()
+        };
+        plugin.load_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(plugin.save_bytes().unwrap(), bytes);
+
+        // edits are kept, even if the record would save the same as before
+        let creature = plugin.objects_of_type_mut::<Creature>().next().unwrap();
+        assert_eq!(creature.scale, Some(3.0));
+        creature.scale = Some(2.0);
+        let saved = plugin.save_bytes().unwrap();
+        assert_eq!(saved[position..position + 4], 2.0f32.to_le_bytes());
+
+        // records are not written with their original bytes in another encoding
+        plugin.load_bytes_with_options(&bytes, &options).unwrap();
+        let options = SaveOptions {
+            encoding: TextEncoding::Windows1251,
+        };
+        assert_eq!(plugin.save_bytes_with_options(&options).unwrap(), saved);
+    }
+}
@@ -9,6 +9,52 @@ use crate::prelude::*;
 #[derive(Clone, Debug, Default)]
 pub struct Plugin {
     pub objects: Vec<TES3Object>,
+    /// An optional lookup table for finding objects by id. See [`Plugin::build_index`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) index: Option<ObjectIndex>,
+    /// Tombstones added by [`Plugin::delete_master_record`], so that undeleting removes them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) tombstones: Vec<TES3Object>,
+    /// The encoding the plugin was loaded with. See [`Plugin::encoding`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) encoding: TextEncoding,
+}
+
+/// Options for loading a [`Plugin`].
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// The encoding used to decode strings.
+    pub encoding: TextEncoding,
+    /// Guess the encoding from the file contents, ignoring `encoding`.
+    pub detect_encoding: bool,
+    /// Keep records and subrecords with unrecognized tags instead of failing.
+    ///
+    /// Unrecognized records load as [`TES3Object::Unknown`], and unrecognized subrecords are kept
+    /// in the `extra_subrecords` of their record. Both are written back unchanged on save, though
+    /// unrecognized subrecords are moved after the known ones, see [`Subrecord`].
+    pub lenient: bool,
+    /// Remember the bytes of each record, so that records left unmodified are saved back exactly
+    /// as they were loaded. See [`OriginalBytes`].
+    pub keep_original_bytes: bool,
+}
+
+/// Options for saving a [`Plugin`].
+#[derive(Clone, Debug, Default)]
+pub struct SaveOptions {
+    /// The encoding used to encode strings.
+    pub encoding: TextEncoding,
+}
+
+impl LoadOptions {
+    pub fn new() -> Self {
+        default()
+    }
+}
+
+impl SaveOptions {
+    pub fn new() -> Self {
+        default()
+    }
 }
 
 impl Plugin {
@@ -34,9 +80,13 @@ impl Plugin {
 
     pub fn save_path(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
         let mut file = std::fs::File::create(&path)?;
+        self.update_file_type(path.as_ref());
+        file.write_all(&self.save_bytes()?)
+    }
 
+    fn update_file_type(&mut self, path: &Path) {
         if let Some(header) = self.header_mut() {
-            if let Some(extension) = path.as_ref().extension() {
+            if let Some(extension) = path.extension() {
                 if extension.eq_ignore_ascii_case("esp") {
                     header.file_type = FileType::Esp;
                 } else if extension.eq_ignore_ascii_case("esm") {
@@ -44,8 +94,6 @@ impl Plugin {
                 }
             }
         }
-
-        file.write_all(&self.save_bytes()?)
     }
 
     pub fn load_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
@@ -57,6 +105,41 @@ impl Plugin {
     }
 
     pub fn load_bytes_filtered(&mut self, bytes: &[u8], filter: impl Fn([u8; 4]) -> bool) -> io::Result<()> {
+        self.load_bytes_impl(bytes, filter, TextEncoding::default(), &LoadOptions::default())
+    }
+
+    /// Load a plugin from `path` with the given options, returning the encoding that was used.
+    pub fn load_path_with_options(&mut self, path: impl AsRef<Path>, options: &LoadOptions) -> io::Result<TextEncoding> {
+        self.load_bytes_with_options(&std::fs::read(path)?, options)
+    }
+
+    /// Load a plugin from `bytes` with the given options, returning the encoding that was used.
+    pub fn load_bytes_with_options(&mut self, bytes: &[u8], options: &LoadOptions) -> io::Result<TextEncoding> {
+        let encoding = if options.detect_encoding {
+            TextEncoding::detect(bytes)
+        } else {
+            options.encoding
+        };
+        self.load_bytes_impl(bytes, |_| true, encoding, options)?;
+        Ok(encoding)
+    }
+
+    fn load_bytes_impl(
+        &mut self,
+        bytes: &[u8],
+        filter: impl Fn([u8; 4]) -> bool,
+        encoding: TextEncoding,
+        options: &LoadOptions,
+    ) -> io::Result<()> {
+        self.encoding = encoding;
+        self.tombstones.clear();
+        let encoding = encoding.encoding();
+        let reader = |bytes| Reader {
+            encoding,
+            lenient: options.lenient,
+            ..Reader::new(bytes)
+        };
+
         let mut stream = Reader::new(bytes);
 
         // do a quick pass calculating the positions of objects
@@ -77,7 +160,10 @@ impl Plugin {
             use rayon::prelude::*;
             self.objects = offsets
                 .into_par_iter()
-                .map(|range| Reader::new(&bytes[range]).load())
+                .enumerate()
+                .map(|(index, range)| {
+                    load_record(reader(&bytes[range.clone()]), index, range.start, options.keep_original_bytes)
+                })
                 .collect::<io::Result<_>>()?;
         }
 
@@ -86,15 +172,33 @@ impl Plugin {
         {
             self.objects = offsets
                 .into_iter()
-                .map(|range| Reader::new(&bytes[range]).load())
+                .enumerate()
+                .map(|(index, range)| {
+                    load_record(reader(&bytes[range.clone()]), index, range.start, options.keep_original_bytes)
+                })
                 .collect::<io::Result<_>>()?;
         }
 
+        if self.index.is_some() {
+            self.build_index();
+        }
+
         Ok(())
     }
 
     pub fn save_bytes(&mut self) -> io::Result<Vec<u8>> {
+        self.save_bytes_with_options(&SaveOptions::default())
+    }
+
+    pub fn save_path_with_options(&mut self, path: impl AsRef<Path>, options: &SaveOptions) -> io::Result<()> {
+        let mut file = std::fs::File::create(&path)?;
+        self.update_file_type(path.as_ref());
+        file.write_all(&self.save_bytes_with_options(options)?)
+    }
+
+    pub fn save_bytes_with_options(&mut self, options: &SaveOptions) -> io::Result<Vec<u8>> {
         let mut stream = Writer::new(vec![]);
+        stream.encoding = options.encoding.encoding();
 
         // update header
         let num_objects = self.objects.len();
@@ -106,12 +210,22 @@ impl Plugin {
 
         // write objects
         for object in &self.objects {
-            stream.save(object)?;
+            // unmodified records are written exactly as they were loaded
+            match object.original_bytes().matching(object, stream.encoding) {
+                Some(original) => stream.save_bytes(original)?,
+                None => stream.save(object)?,
+            }
         }
 
         Ok(stream.cursor.into_inner())
     }
 
+    /// The encoding strings were decoded with when the plugin was loaded, which is Windows-1252
+    /// for plugins that were not loaded from a file.
+    pub const fn encoding(&self) -> TextEncoding {
+        self.encoding
+    }
+
     pub fn header(&self) -> Option<&Header> {
         self.objects_of_type().next()
     }
@@ -138,6 +252,7 @@ impl Plugin {
     where
         &'a mut TES3Object: TryInto<&'a mut T>,
     {
+        self.invalidate_index();
         self.objects.iter_mut().filter_map(|object| object.try_into().ok())
     }
 
@@ -147,8 +262,68 @@ impl Plugin {
         TES3Object: TryInto<T>,
         for<'a> &'a mut TES3Object: TryInto<&'a mut T>,
     {
+        self.invalidate_index();
         self.objects
             .extract_if(.., |obj| obj.try_into().is_ok())
             .filter_map(|obj| obj.try_into().ok())
     }
 }
+
+/// Load the record in `stream`, attaching its location within the plugin to any error.
+fn load_record(mut stream: Reader<'_>, index: usize, start: usize, keep_original_bytes: bool) -> io::Result<TES3Object> {
+    let mut object: TES3Object = stream.load().map_err(|error| {
+        let record = *stream.cursor.get_ref();
+        let mut error = LoadError::from(error);
+        let offset = error.offset.unwrap_or_else(|| stream.cursor.position());
+
+        error.record_index.get_or_insert(index);
+        if error.record_tag.is_none() {
+            error.record_tag = record.get(..4).map(|tag| tag.to_str_lossy().into_owned());
+        }
+
+        // find the record id and the subrecord that failed
+        let id_tag = if record.starts_with(DialogueInfo::TAG) {
+            b"INAM"
+        } else {
+            b"NAME"
+        };
+        let mut position = 16;
+        while let Some((tag, size)) = record.get(position..position + 8).map(|header| header.split_at(4)) {
+            let end = position + 8 + u32::from_le_bytes(size.try_into().unwrap_or_default()) as usize;
+            if error.subrecord_tag.is_none() && (position as u64..end as u64).contains(&offset) {
+                error.subrecord_tag = tag.try_into().ok();
+            }
+            if error.record_id.is_none() && tag == id_tag {
+                if let Some(data) = record.get(position + 8..end) {
+                    let data = data.split(|&byte| byte == 0).next().unwrap_or_default();
+                    error.record_id = Some(stream.encoding.decode_without_bom_handling(data).0.into_owned());
+                }
+            }
+            position = end;
+        }
+
+        error.offset = Some(start as u64 + offset);
+        io::Error::from(error)
+    })?;
+
+    if keep_original_bytes {
+        *object.original_bytes_mut() = OriginalBytes::new(&stream);
+    }
+
+    Ok(object)
+}
+
+#[cfg(test)]
+impl Plugin {
+    /// A plugin holding a header that lists `masters` with their sizes, followed by `objects`.
+    pub(crate) fn with_masters(masters: &[(&str, u64)], objects: impl IntoIterator<Item = TES3Object>) -> Self {
+        let header = Header {
+            masters: masters.iter().map(|&(name, size)| (name.to_owned(), size)).collect(),
+            ..default()
+        };
+        Self {
+            objects: std::iter::once(header.into()).chain(objects).collect(),
+            ..default()
+        }
+    }
+}
@@ -10,6 +10,10 @@ pub struct Race {
     pub spells: Vec<String>,
     pub description: String,
     pub data: RaceData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -63,7 +67,7 @@ impl Load for Race {
                     this.name = stream.load()?;
                 }
                 b"RADT" => {
-                    stream.expect(140u32)?;
+                    stream.expect_size(140)?;
                     this.data = stream.load()?;
                 }
                 b"NPCS" => {
@@ -78,7 +82,8 @@ impl Load for Race {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -119,6 +124,10 @@ impl Save for Race {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -1,3 +1,6 @@
+// external imports
+use glam::{Affine3A, Quat, Vec3};
+
 // internal imports
 use crate::prelude::*;
 
@@ -26,6 +29,8 @@ pub struct Reference {
     pub soul: Option<String>,
     pub blocked: Option<u8>,
     pub deleted: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
 }
 
 impl Load for Reference {
@@ -38,11 +43,11 @@ impl Load for Reference {
                     this.id = stream.load()?;
                 }
                 b"UNAM" => {
-                    stream.expect(1u32)?;
+                    stream.expect_size(1)?;
                     this.blocked = Some(stream.load()?);
                 }
                 b"XSCL" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.scale = Some(stream.load()?);
                 }
                 b"ANAM" => {
@@ -55,30 +60,30 @@ impl Load for Reference {
                     this.owner_faction = Some(stream.load()?);
                 }
                 b"INDX" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.owner_faction_rank = Some(stream.load()?);
                 }
                 b"XSOL" => {
                     this.soul = Some(stream.load()?);
                 }
                 b"XCHG" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.charge_left = Some(stream.load()?);
                 }
                 b"INTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.health_left = Some(stream.load()?);
                 }
                 b"NAM9" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.object_count = Some(stream.load()?);
                 }
                 b"DODT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.destination = Some(stream.load()?);
                 }
                 b"FLTV" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.lock_level = Some(stream.load()?);
                 }
                 b"KNAM" => {
@@ -88,7 +93,7 @@ impl Load for Reference {
                     this.trap = Some(stream.load()?);
                 }
                 b"DATA" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.translation = stream.load()?;
                     this.rotation = stream.load()?;
                     break;
@@ -100,7 +105,7 @@ impl Load for Reference {
                     break;
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: REFR::{}", tag.to_str_lossy()))?;
+                    this.extra_subrecords.push(Subrecord::load_unexpected(stream, tag, "REFR")?);
                 }
             }
         }
@@ -202,6 +207,11 @@ impl Save for Reference {
             stream.save(b"TNAM")?;
             stream.save(value)?;
         }
+        // extra subrecords
+        // must come before DATA/DELE, which end the reference
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         // DELE
         if self.deleted.is_some() {
             stream.save(b"DELE")?;
@@ -242,6 +252,16 @@ impl Reference {
         !self.temporary
     }
 
+    /// The transform from the referenced object's local space into world space.
+    ///
+    /// Rotations are applied in Z, Y, X order using clockwise angles, as done by the engine.
+    pub fn world_transform(&self) -> Affine3A {
+        let [x, y, z] = self.rotation;
+        let rotation = Quat::from_rotation_x(-x) * Quat::from_rotation_y(-y) * Quat::from_rotation_z(-z);
+        let scale = Vec3::splat(self.scale.unwrap_or(1.0));
+        Affine3A::from_scale_rotation_translation(scale, rotation, self.translation.into())
+    }
+
     pub(crate) fn make_transforms_finite(&mut self) {
         for value in &mut self.translation {
             if !value.is_finite() {
@@ -258,3 +278,35 @@ impl Reference {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_world_transform() {
+        let mut reference = Reference {
+            translation: [10.0, 20.0, 30.0],
+            ..default()
+        };
+        let transforms = |reference: &Reference, point: Vec3, expected: [f32; 3]| {
+            let point = reference.world_transform().transform_point3(point);
+            point.abs_diff_eq(expected.into(), 1e-4)
+        };
+        assert!(transforms(&reference, Vec3::X, [11.0, 20.0, 30.0]));
+
+        // angles are clockwise
+        reference.rotation = [0.0, 0.0, FRAC_PI_2];
+        assert!(transforms(&reference, Vec3::X * 10.0, [10.0, 10.0, 30.0]));
+
+        // z is applied before x
+        reference.rotation = [FRAC_PI_2, 0.0, FRAC_PI_2];
+        assert!(transforms(&reference, Vec3::X * 10.0, [10.0, 20.0, 40.0]));
+
+        // scale is applied before rotation
+        reference.scale = Some(2.0);
+        assert!(transforms(&reference, Vec3::X * 10.0, [10.0, 20.0, 50.0]));
+    }
+}
@@ -11,6 +11,10 @@ pub struct Region {
     pub sleep_creature: String,
     pub map_color: [u8; 4],
     pub sounds: Vec<(FixedString<32>, u8)>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -49,11 +53,11 @@ impl Load for Region {
                     this.sleep_creature = stream.load()?;
                 }
                 b"CNAM" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.map_color = stream.load()?;
                 }
                 b"SNAM" => {
-                    stream.expect(33u32)?;
+                    stream.expect_size(33)?;
                     this.sounds.push(stream.load()?);
                 }
                 b"DELE" => {
@@ -62,7 +66,8 @@ impl Load for Region {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -107,6 +112,10 @@ impl Save for Region {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
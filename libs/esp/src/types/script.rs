@@ -10,6 +10,10 @@ pub struct Script {
     pub variables: Vec<u8>,
     pub bytecode: Vec<u8>,
     pub text: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -31,7 +35,7 @@ impl Load for Script {
         while let Ok(tag) = stream.load() {
             match &tag {
                 b"SCHD" => {
-                    stream.expect(52u32)?;
+                    stream.expect_size(52)?;
                     this.id = stream.load::<FixedString<32>>()?.into();
                     this.header = stream.load()?;
                 }
@@ -50,7 +54,8 @@ impl Load for Script {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -88,6 +93,10 @@ impl Save for Script {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -8,6 +8,10 @@ pub struct Skill {
     pub skill_id: SkillId,
     pub data: SkillData,
     pub description: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -27,11 +31,11 @@ impl Load for Skill {
         while let Ok(tag) = stream.load() {
             match &tag {
                 b"INDX" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.skill_id = stream.load()?;
                 }
                 b"SKDT" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.data = stream.load()?;
                 }
                 b"DESC" => {
@@ -43,7 +47,8 @@ impl Load for Skill {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -74,6 +79,10 @@ impl Save for Skill {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -8,6 +8,10 @@ pub struct Sound {
     pub id: String,
     pub sound_path: String,
     pub data: SoundData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -32,7 +36,7 @@ impl Load for Sound {
                     this.sound_path = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(3u32)?;
+                    stream.expect_size(3)?;
                     this.data = stream.load()?;
                 }
                 b"DELE" => {
@@ -41,7 +45,8 @@ impl Load for Sound {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -71,6 +76,10 @@ impl Save for Sound {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -9,6 +9,10 @@ pub struct SoundGen {
     pub sound_gen_type: SoundGenType,
     pub creature: String,
     pub sound: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 impl Load for SoundGen {
@@ -23,7 +27,7 @@ impl Load for SoundGen {
                     this.id = stream.load()?;
                 }
                 b"DATA" => {
-                    stream.expect(4u32)?;
+                    stream.expect_size(4)?;
                     this.sound_gen_type = stream.load()?;
                 }
                 b"CNAM" => {
@@ -38,7 +42,8 @@ impl Load for SoundGen {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -73,6 +78,10 @@ impl Save for SoundGen {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
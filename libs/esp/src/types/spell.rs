@@ -9,6 +9,10 @@ pub struct Spell {
     pub name: String,
     pub effects: Vec<Effect>,
     pub data: SpellData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -34,11 +38,11 @@ impl Load for Spell {
                     this.name = stream.load()?;
                 }
                 b"SPDT" => {
-                    stream.expect(12u32)?;
+                    stream.expect_size(12)?;
                     this.data = stream.load()?;
                 }
                 b"ENAM" => {
-                    stream.expect(24u32)?;
+                    stream.expect_size(24)?;
                     this.effects.push(stream.load()?);
                 }
                 b"DELE" => {
@@ -47,7 +51,8 @@ impl Load for Spell {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -83,6 +88,10 @@ impl Save for Spell {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
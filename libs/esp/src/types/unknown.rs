@@ -0,0 +1,142 @@
+// rust std imports
+use std::io::Read;
+
+// internal imports
+use crate::prelude::*;
+
+/// A record with a tag this crate does not recognize, kept as raw bytes.
+///
+/// Only produced when loading in lenient mode. See [`LoadOptions::lenient`].
+#[esp_meta]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Unknown {
+    pub flags: ObjectFlags,
+    pub tag: [u8; 4],
+    /// The unused field of the record header, which is zero in files written by the Construction Set.
+    pub unused: u32,
+    pub bytes: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
+}
+
+/// A subrecord that its record type does not recognize, kept as raw bytes.
+///
+/// Their position among the known subrecords is not kept. They are saved in the order they were
+/// loaded, after every known subrecord of the record, except for references, where they are saved
+/// before the `DELE` or `DATA` subrecord that ends the reference. Load with
+/// [`LoadOptions::keep_original_bytes`] to save unmodified records exactly as they were.
+///
+/// [`LoadOptions::keep_original_bytes`]: crate::types::LoadOptions::keep_original_bytes
+#[esp_meta]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Subrecord {
+    pub tag: [u8; 4],
+    pub bytes: Vec<u8>,
+}
+
+impl Load for Unknown {
+    fn load(stream: &mut Reader<'_>) -> io::Result<Self> {
+        let mut this: Self = default();
+
+        this.flags = stream.load()?;
+        stream.read_to_end(&mut this.bytes)?;
+
+        Ok(this)
+    }
+}
+
+impl Save for Unknown {
+    fn save(&self, stream: &mut Writer) -> io::Result<()> {
+        stream.save(&self.flags)?;
+        stream.save_bytes(&self.bytes)?;
+        Ok(())
+    }
+}
+
+impl Subrecord {
+    /// Load a subrecord with a tag that `record_tag` does not recognize.
+    ///
+    /// Fails unless the reader is lenient, in which case the raw contents are kept.
+    pub fn load_unexpected(stream: &mut Reader<'_>, tag: [u8; 4], record_tag: &str) -> io::Result<Self> {
+        if !stream.lenient {
+            let mut error = LoadError::new(LoadErrorKind::UnexpectedTag(tag));
+            error.offset = Some(stream.cursor.position());
+            error.record_tag = Some(record_tag.to_owned());
+            error.subrecord_tag = Some(tag);
+            return Err(error.into());
+        }
+        let size: u32 = stream.load()?;
+        Ok(Self {
+            tag,
+            bytes: stream.load_bytes(size as usize)?,
+        })
+    }
+}
+
+impl Save for Subrecord {
+    fn save(&self, stream: &mut Writer) -> io::Result<()> {
+        stream.save(&self.tag)?;
+        stream.save_as::<usize, u32>(self.bytes.len())?;
+        stream.save_bytes(&self.bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tag: [u8; 4], unused: u32, body: &[u8]) -> Vec<u8> {
+        let mut bytes = tag.to_vec();
+        bytes.extend_from_slice(&u32::try_from(body.len() - 4).unwrap().to_le_bytes());
+        bytes.extend_from_slice(&unused.to_le_bytes());
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    #[test]
+    fn test_lenient_round_trip() {
+        let mut plugin = Plugin {
+            objects: vec![GlobalVariable {
+                id: "Gold".into(),
+                value: GlobalValue::Long(5),
+                ..default()
+            }
+            .into()],
+            ..default()
+        };
+        let saved = plugin.save_bytes().unwrap();
+
+        // a known record with an unexpected subrecord, followed by a record of an unknown type
+        let mut global = saved[12..].to_vec();
+        global.extend_from_slice(b"XTRA\x03\x00\x00\x00abc");
+        let mut bytes = record(*GlobalVariable::TAG, 0, &global);
+        bytes.extend(record(*b"ZZZZ", 7, b"\x00\x04\x00\x00DATA\x01\x00\x00\x00\xff"));
+
+        assert!(Plugin::new().load_bytes(&bytes).is_err());
+
+        let mut plugin = Plugin::new();
+        let options = LoadOptions {
+            lenient: true,
+            ..default()
+        };
+        plugin.load_bytes_with_options(&bytes, &options).unwrap();
+
+        let global = plugin.objects_of_type::<GlobalVariable>().next().unwrap();
+        assert_eq!(
+            global.extra_subrecords,
+            [Subrecord {
+                tag: *b"XTRA",
+                bytes: b"abc".to_vec(),
+            }]
+        );
+        let unknown = plugin.objects_of_type::<Unknown>().next().unwrap();
+        assert_eq!(unknown.tag(), b"ZZZZ");
+        assert_eq!(plugin.objects[1].tag_str(), "ZZZZ");
+        assert_eq!(unknown.unused, 7);
+        assert!(unknown.flags.contains(ObjectFlags::PERSISTENT));
+        assert_eq!(unknown.bytes, b"DATA\x01\x00\x00\x00\xff");
+
+        assert_eq!(plugin.save_bytes().unwrap(), bytes);
+    }
+}
@@ -12,6 +12,10 @@ pub struct Weapon {
     pub icon: String,
     pub enchanting: String,
     pub data: WeaponData,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub extra_subrecords: Vec<Subrecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_bytes: OriginalBytes,
 }
 
 #[esp_meta]
@@ -51,7 +55,7 @@ impl Load for Weapon {
                     this.name = stream.load()?;
                 }
                 b"WPDT" => {
-                    stream.expect(32u32)?;
+                    stream.expect_size(32)?;
                     this.data = stream.load()?;
                 }
                 b"SCRI" => {
@@ -69,7 +73,8 @@ impl Load for Weapon {
                     this.flags.insert(ObjectFlags::DELETED);
                 }
                 _ => {
-                    Reader::error(format!("Unexpected Tag: {}::{}", this.tag_str(), tag.to_str_lossy()))?;
+                    this.extra_subrecords
+                        .push(Subrecord::load_unexpected(stream, tag, Self::TAG_STR)?);
                 }
             }
         }
@@ -119,6 +124,10 @@ impl Save for Weapon {
             stream.save(&4u32)?;
             stream.save(&0u32)?;
         }
+        // extra subrecords
+        for subrecord in &self.extra_subrecords {
+            stream.save(subrecord)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,66 @@
+mod assets;
+pub use assets::*;
+
+mod cell_assignment;
+pub use cell_assignment::*;
+
+#[cfg(feature = "conflicts")]
+mod conflicts;
+#[cfg(feature = "conflicts")]
+pub use conflicts::*;
+
+mod csv;
+
+mod encoding;
+pub use encoding::*;
+
+mod localization;
+pub use localization::*;
+
+mod game_settings;
+pub use game_settings::*;
+
+mod lint;
+pub use lint::*;
+
+mod load_order;
+pub use load_order::*;
+
+mod master_conversion;
+pub use master_conversion::*;
+
+mod master_sync;
+pub use master_sync::*;
+
+mod object_index;
+pub use object_index::*;
+
+mod pathgrid_generator;
+pub use pathgrid_generator::*;
+
+mod pathgrid_graph;
+pub use pathgrid_graph::*;
+
+#[cfg(feature = "query")]
+mod query;
+#[cfg(feature = "query")]
+pub use query::*;
+
+mod reference_index;
+pub use reference_index::*;
+
+mod rename;
+pub use rename::*;
+
+mod script_text;
+
+mod spatial_index;
+pub use spatial_index::*;
+
+mod terrain_export;
+pub use terrain_export::*;
+
+mod tombstone;
+
+mod world_map;
+pub use world_map::*;
@@ -0,0 +1,305 @@
+// rust std imports
+use std::collections::BTreeMap;
+use std::io::Write;
+
+// internal imports
+use crate::prelude::*;
+use crate::utils::csv::csv_quote;
+
+/// The kind of file an asset path refers to, which determines the directory it is relative to.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AssetKind {
+    Mesh,
+    Icon,
+    Texture,
+    Sound,
+}
+
+impl AssetKind {
+    /// The directory under `Data Files` that paths of this kind are relative to.
+    pub const fn directory(self) -> &'static str {
+        match self {
+            Self::Mesh => "meshes",
+            Self::Icon => "icons",
+            Self::Texture => "textures",
+            Self::Sound => "sound",
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Mesh => "mesh",
+            Self::Icon => "icon",
+            Self::Texture => "texture",
+            Self::Sound => "sound",
+        }
+    }
+
+    /// Resolve a path as written in a record or mesh to a path relative to `Data Files`.
+    ///
+    /// Forward slashes become backslashes, and the kind's directory is prepended unless the path
+    /// already starts with it, e.g. `x\ex_hlaalu_b_01.nif` becomes `meshes\x\ex_hlaalu_b_01.nif`.
+    pub fn resolve(self, path: &str) -> String {
+        let path = path.trim().replace('/', "\\");
+        let path = path.trim_start_matches('\\');
+
+        let directory = self.directory();
+        let has_directory = path
+            .get(..directory.len() + 1)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{directory}\\")));
+
+        if has_directory {
+            path.to_owned()
+        } else {
+            format!("{directory}\\{path}")
+        }
+    }
+}
+
+/// Something that uses an asset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetUser {
+    /// The tag of the record using the asset, e.g. `WEAP`.
+    pub tag: String,
+    /// The id of the record using the asset.
+    pub id: String,
+    /// The field of the record that refers to the asset, e.g. `mesh`.
+    pub field: &'static str,
+    /// The mesh through which the record uses the asset, for textures used by meshes.
+    pub mesh: Option<String>,
+}
+
+/// A file under `Data Files` that is needed by a plugin.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Asset {
+    pub kind: AssetKind,
+    /// The path relative to `Data Files`, see [`AssetKind::resolve`].
+    pub path: String,
+    pub users: Vec<AssetUser>,
+}
+
+impl Asset {
+    /// The files the game will look for, in order of preference.
+    ///
+    /// Textures and icons are replaced with a `.dds` file of the same name when one exists.
+    pub fn candidates(&self) -> Vec<String> {
+        let mut candidates = vec![];
+        if matches!(self.kind, AssetKind::Icon | AssetKind::Texture) {
+            if let Some((stem, extension)) = self.path.rsplit_once('.') {
+                if !extension.eq_ignore_ascii_case("dds") {
+                    candidates.push(format!("{stem}.dds"));
+                }
+            }
+        }
+        candidates.push(self.path.clone());
+        candidates
+    }
+}
+
+/// Every asset needed by a set of plugins, along with the records that use them.
+///
+/// Assets are identified by path, ignoring ascii case. Textures used within meshes are not found
+/// by [`AssetManifest::add_plugin`], as that requires reading the meshes. The `tes3` crate can add
+/// them when built with both the `esp` and `nif` features.
+#[derive(Clone, Debug, Default)]
+pub struct AssetManifest {
+    assets: BTreeMap<String, Asset>,
+}
+
+impl AssetManifest {
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// The assets used by the records of `plugin`.
+    pub fn from_plugin(plugin: &Plugin) -> Self {
+        let mut this = Self::new();
+        this.add_plugin(plugin);
+        this
+    }
+
+    /// Add the assets used by the records of `plugin`.
+    pub fn add_plugin(&mut self, plugin: &Plugin) {
+        for object in &plugin.objects {
+            // deleted records are not loaded, so neither are their assets
+            if object.deleted() {
+                continue;
+            }
+            for (kind, field, path) in asset_fields(object) {
+                let user = AssetUser {
+                    tag: object.tag_str().to_owned(),
+                    id: object.editor_id().into_owned(),
+                    field,
+                    mesh: None,
+                };
+                self.insert(kind, path, user);
+            }
+        }
+    }
+
+    /// Record that `user` needs the asset at `path`, which is resolved according to `kind`.
+    /// Empty paths are ignored.
+    pub fn insert(&mut self, kind: AssetKind, path: &str, user: AssetUser) {
+        if path.trim().is_empty() {
+            return;
+        }
+        let path = kind.resolve(path);
+        self.assets
+            .entry(path.to_ascii_lowercase())
+            .or_insert_with(|| Asset {
+                kind,
+                path,
+                users: vec![],
+            })
+            .users
+            .push(user);
+    }
+
+    /// The asset at `path` (relative to `Data Files`, ignoring ascii case).
+    pub fn get(&self, path: &str) -> Option<&Asset> {
+        self.assets.get(&path.replace('/', "\\").to_ascii_lowercase())
+    }
+
+    /// All assets, ordered by path.
+    pub fn assets(&self) -> impl Iterator<Item = &Asset> {
+        self.assets.values()
+    }
+
+    pub fn assets_of_kind(&self, kind: AssetKind) -> impl Iterator<Item = &Asset> {
+        self.assets().filter(move |asset| asset.kind == kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Write every use of every asset as CSV with `kind,path,tag,id,field,mesh` columns.
+    pub fn save_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "kind,path,tag,id,field,mesh")?;
+        for asset in self.assets() {
+            for user in &asset.users {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{}",
+                    asset.kind.name(),
+                    csv_quote(&asset.path),
+                    user.tag,
+                    csv_quote(&user.id),
+                    user.field,
+                    csv_quote(user.mesh.as_deref().unwrap_or_default()),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The asset paths of `object` as `(kind, field, path)`.
+fn asset_fields(object: &TES3Object) -> Vec<(AssetKind, &'static str, &str)> {
+    use AssetKind::{Icon, Mesh, Sound, Texture};
+
+    macro_rules! fields {
+        ($object:ident, $($kind:ident $field:ident),*) => {
+            vec![$(($kind, stringify!($field), $object.$field.as_str())),*]
+        };
+    }
+
+    match object {
+        TES3Object::Activator(object) => fields!(object, Mesh mesh),
+        TES3Object::Alchemy(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Apparatus(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Armor(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Birthsign(object) => fields!(object, Texture texture),
+        TES3Object::Bodypart(object) => fields!(object, Mesh mesh),
+        TES3Object::Book(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Clothing(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Container(object) => fields!(object, Mesh mesh),
+        TES3Object::Creature(object) => fields!(object, Mesh mesh),
+        TES3Object::DialogueInfo(object) => fields!(object, Sound sound_path),
+        TES3Object::Door(object) => fields!(object, Mesh mesh),
+        TES3Object::Ingredient(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::LandscapeTexture(object) => fields!(object, Texture file_name),
+        TES3Object::Light(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Lockpick(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::MagicEffect(object) => fields!(object, Icon icon, Texture texture),
+        TES3Object::MiscItem(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Npc(object) => fields!(object, Mesh mesh),
+        TES3Object::Probe(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::RepairItem(object) => fields!(object, Mesh mesh, Icon icon),
+        TES3Object::Sound(object) => fields!(object, Sound sound_path),
+        TES3Object::Static(object) => fields!(object, Mesh mesh),
+        TES3Object::Weapon(object) => fields!(object, Mesh mesh, Icon icon),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        assert_eq!(
+            AssetKind::Mesh.resolve("x\\ex_hlaalu_b_01.nif"),
+            "meshes\\x\\ex_hlaalu_b_01.nif"
+        );
+        assert_eq!(AssetKind::Texture.resolve("Textures/tx_a.tga"), "Textures\\tx_a.tga");
+        assert_eq!(
+            AssetKind::Sound.resolve("\\Vo\\a\\f\\hlo_af000.mp3"),
+            "sound\\Vo\\a\\f\\hlo_af000.mp3"
+        );
+    }
+
+    #[test]
+    fn manifest() {
+        let plugin = Plugin {
+            objects: vec![
+                Weapon {
+                    id: "iron_club".into(),
+                    mesh: "w\\W_Club_Iron.nif".into(),
+                    icon: "w\\tx_club_iron.tga".into(),
+                    ..default()
+                }
+                .into(),
+                Static {
+                    id: "club_display".into(),
+                    mesh: "W\\w_club_iron.NIF".into(),
+                    ..default()
+                }
+                .into(),
+                DialogueInfo {
+                    id: "1".into(),
+                    sound_path: "Vo\\a\\f\\hlo_af000.mp3".into(),
+                    ..default()
+                }
+                .into(),
+                Activator::default().into(),
+            ],
+            ..default()
+        };
+
+        let manifest = AssetManifest::from_plugin(&plugin);
+        assert_eq!(manifest.len(), 3);
+
+        let mesh = manifest.get("meshes/w/w_club_iron.nif").unwrap();
+        assert_eq!(mesh.path, "meshes\\w\\W_Club_Iron.nif");
+        let users: Vec<_> = mesh.users.iter().map(|user| (user.id.as_str(), user.field)).collect();
+        assert_eq!(users, [("iron_club", "mesh"), ("club_display", "mesh")]);
+
+        let icon = manifest.assets_of_kind(AssetKind::Icon).next().unwrap();
+        assert_eq!(
+            icon.candidates(),
+            ["icons\\w\\tx_club_iron.dds", "icons\\w\\tx_club_iron.tga"]
+        );
+
+        let mut csv = vec![];
+        manifest.save_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("sound,sound\\Vo\\a\\f\\hlo_af000.mp3,INFO,1,sound_path,\n"));
+    }
+}
@@ -0,0 +1,222 @@
+// internal imports
+use crate::prelude::*;
+
+/// A change made by [`Plugin::fix_cell_assignment`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CellReassignment {
+    pub mast_index: u32,
+    pub refr_index: u32,
+    pub id: String,
+    /// The cell the reference was listed in.
+    pub from: (i32, i32),
+    /// The cell that contains the reference's position.
+    pub to: (i32, i32),
+    pub action: CellReassignmentAction,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CellReassignmentAction {
+    /// The reference was moved into the references of the correct cell.
+    Moved,
+    /// The reference was moved into a newly created cell record.
+    MovedToNewCell,
+    /// The reference belongs to a master, so its `moved_cell` was updated instead.
+    MarkedMoved,
+    /// The reference is in the cell it is listed in, so its stale `moved_cell` was cleared.
+    ClearedMoved,
+    /// The correct cell already has a reference with the same indices, so nothing was changed.
+    Conflict,
+}
+
+impl Plugin {
+    /// Ensure every exterior reference is listed in the cell whose area contains it.
+    ///
+    /// References defined by this plugin (`mast_index` 0) are moved between cells, creating new
+    /// exterior cells where required. References defined by masters cannot be moved, so their
+    /// `moved_cell` is updated instead.
+    pub fn fix_cell_assignment(&mut self) -> Vec<CellReassignment> {
+        let mut report = vec![];
+        let mut moving = vec![];
+
+        for cell in self.objects_of_type_mut::<Cell>().filter(|cell| cell.is_exterior()) {
+            let grid = cell.data.grid;
+
+            let mut keys: Vec<_> = cell.references.keys().copied().collect();
+            keys.sort_unstable();
+
+            for key in keys {
+                let Some(reference) = cell.references.get_mut(&key) else {
+                    continue;
+                };
+                if reference.deleted() {
+                    continue;
+                }
+
+                let target = exterior_grid_of(reference.translation);
+
+                let action = if key.0 == 0 && target != grid {
+                    if let Some(reference) = cell.references.remove(&key) {
+                        moving.push((grid, target, key, reference));
+                    }
+                    continue;
+                } else if target == grid {
+                    if reference.moved_cell.take().is_none() {
+                        continue;
+                    }
+                    CellReassignmentAction::ClearedMoved
+                } else {
+                    if reference.moved_cell.replace(target) == Some(target) {
+                        continue;
+                    }
+                    CellReassignmentAction::MarkedMoved
+                };
+
+                report.push(CellReassignment {
+                    mast_index: key.0,
+                    refr_index: key.1,
+                    id: reference.id.clone(),
+                    from: grid,
+                    to: target,
+                    action,
+                });
+            }
+        }
+
+        for (from, to, key, mut reference) in moving {
+            reference.moved_cell = None;
+
+            let mut action = CellReassignmentAction::Moved;
+            if self.exterior_cell(to).is_none() {
+                action = CellReassignmentAction::MovedToNewCell;
+                self.objects.push(TES3Object::Cell(Cell {
+                    data: CellData { grid: to, ..default() },
+                    ..default()
+                }));
+            }
+            let Some(cell) = self.exterior_cell_mut(to) else {
+                continue;
+            };
+
+            let id = reference.id.clone();
+            if cell.references.contains_key(&key) {
+                // put it back where it came from
+                action = CellReassignmentAction::Conflict;
+                if let Some(cell) = self.exterior_cell_mut(from) {
+                    cell.references.insert(key, reference);
+                }
+            } else {
+                cell.references.insert(key, reference);
+            }
+
+            report.push(CellReassignment {
+                mast_index: key.0,
+                refr_index: key.1,
+                id,
+                from,
+                to,
+                action,
+            });
+        }
+
+        report
+    }
+
+    /// The exterior cell record with the given grid coordinates.
+    pub fn exterior_cell(&self, grid: (i32, i32)) -> Option<&Cell> {
+        self.objects_of_type::<Cell>()
+            .find(|cell| cell.exterior_coords() == Some(grid))
+    }
+
+    /// The exterior cell record with the given grid coordinates.
+    pub fn exterior_cell_mut(&mut self, grid: (i32, i32)) -> Option<&mut Cell> {
+        self.objects_of_type_mut::<Cell>()
+            .find(|cell| cell.exterior_coords() == Some(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `mast_index`, `refr_index`, `translation` and `moved_cell` of a reference.
+    type Placement = (u32, u32, [f32; 3], Option<(i32, i32)>);
+
+    fn exterior(grid: (i32, i32), references: &[Placement]) -> TES3Object {
+        let mut cell = Cell {
+            data: CellData { grid, ..default() },
+            ..default()
+        };
+        for &(mast_index, refr_index, translation, moved_cell) in references {
+            let reference = Reference {
+                mast_index,
+                refr_index,
+                id: format!("ref_{mast_index}_{refr_index}"),
+                translation,
+                moved_cell,
+                ..default()
+            };
+            cell.references.insert((mast_index, refr_index), reference);
+        }
+        cell.into()
+    }
+
+    #[test]
+    fn fix_cell_assignment() {
+        let mut plugin = Plugin::new();
+        plugin.objects.extend([
+            exterior(
+                (0, 0),
+                &[
+                    // in place
+                    (0, 1, [100.0, 100.0, 0.0], None),
+                    // in place, with a stale moved_cell
+                    (0, 2, [100.0, 100.0, 0.0], Some((1, 0))),
+                    // moved into an existing cell
+                    (0, 3, [9000.0, 100.0, 0.0], None),
+                    // moved into a new cell
+                    (0, 4, [-100.0, -100.0, 0.0], None),
+                    // conflicts with a reference of the target cell
+                    (0, 5, [9000.0, 100.0, 0.0], None),
+                    // master references
+                    (1, 1, [9000.0, 100.0, 0.0], None),
+                    (1, 2, [100.0, 100.0, 0.0], Some((1, 0))),
+                    (1, 3, [9000.0, 100.0, 0.0], Some((1, 0))),
+                ],
+            ),
+            exterior((1, 0), &[(0, 5, [9000.0, 200.0, 0.0], None)]),
+        ]);
+
+        let report = plugin.fix_cell_assignment();
+        let actions: Vec<_> = report
+            .iter()
+            .map(|change| (change.mast_index, change.refr_index, change.to, change.action))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                (0, 2, (0, 0), CellReassignmentAction::ClearedMoved),
+                (1, 1, (1, 0), CellReassignmentAction::MarkedMoved),
+                (1, 2, (0, 0), CellReassignmentAction::ClearedMoved),
+                (0, 3, (1, 0), CellReassignmentAction::Moved),
+                (0, 4, (-1, -1), CellReassignmentAction::MovedToNewCell),
+                (0, 5, (1, 0), CellReassignmentAction::Conflict),
+            ]
+        );
+
+        let cell = plugin.exterior_cell((0, 0)).unwrap();
+        let mut keys: Vec<_> = cell.references.keys().copied().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [(0, 1), (0, 2), (0, 5), (1, 1), (1, 2), (1, 3)]);
+        assert_eq!(cell.references[&(0, 2)].moved_cell, None);
+        assert_eq!(cell.references[&(1, 1)].moved_cell, Some((1, 0)));
+        assert_eq!(cell.references[&(1, 2)].moved_cell, None);
+
+        assert!(plugin.exterior_cell((1, 0)).unwrap().references.contains_key(&(0, 3)));
+        assert!(plugin.exterior_cell((-1, -1)).unwrap().references.contains_key(&(0, 4)));
+
+        // everything but the conflict is now in place
+        let report = plugin.fix_cell_assignment();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].action, CellReassignmentAction::Conflict);
+    }
+}
@@ -0,0 +1,380 @@
+// rust std imports
+use std::collections::BTreeMap;
+use std::io::Write;
+
+// external imports
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// internal imports
+use crate::prelude::*;
+
+/// A record defined or overridden by more than one plugin.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecordConflict {
+    /// The record tag, e.g. `NPC_`.
+    pub tag: String,
+    /// The record id, as written by the winner.
+    pub id: String,
+    /// The plugin whose version the game uses, which is the last to define the record.
+    pub winner: String,
+    /// The other plugins defining the record, in load order.
+    pub losers: Vec<String>,
+    /// The fields that differ between the versions, as dotted paths following the record's
+    /// serialized structure, e.g. `data.level`. Empty if every version is identical.
+    pub fields: Vec<String>,
+}
+
+/// A reference changed by more than one plugin.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReferenceConflict {
+    /// The plugin that defines the reference, in which its `mast_index` is 0.
+    pub master: String,
+    pub refr_index: u32,
+    /// The id of the cell containing the reference, according to the winner.
+    pub cell: String,
+    /// The plugin whose version the game uses, which is the last to change the reference.
+    pub winner: String,
+    /// The other plugins changing the reference, in load order.
+    pub losers: Vec<String>,
+    /// The fields that differ between the versions, see [`RecordConflict::fields`].
+    pub fields: Vec<String>,
+}
+
+/// Every record and reference changed by more than one plugin of a load order.
+///
+/// ```ignore
+/// let report = ConflictReport::new(&[("Morrowind.esm", &morrowind), ("My Mod.esp", &my_mod)]);
+/// report.save_text(std::io::stdout())?;
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ConflictReport {
+    /// Conflicting records, ordered by tag and id.
+    pub records: Vec<RecordConflict>,
+    /// Conflicting references, ordered by the plugin defining them and `refr_index`.
+    pub references: Vec<ReferenceConflict>,
+}
+
+/// The versions of a record or reference, as `(plugin position, version)` in load order.
+type Versions<T> = Vec<(usize, T)>;
+
+impl ConflictReport {
+    /// Find the conflicts between `plugins`, given with their file names in load order.
+    ///
+    /// Records are matched by tag and id ignoring ascii case, with exterior cells matched by grid.
+    /// References are matched by the plugin defining them and their `refr_index`, resolving each
+    /// `mast_index` through the masters of the plugin it is found in. The references of a cell
+    /// are compared separately from the cell record, and `mast_index` is not compared.
+    pub fn new(plugins: &[(&str, &Plugin)]) -> Self {
+        let mut records: BTreeMap<_, Versions<&TES3Object>> = BTreeMap::new();
+        let mut references: BTreeMap<_, (&str, Versions<(&Cell, &Reference)>)> = BTreeMap::new();
+
+        for (position, (name, plugin)) in plugins.iter().enumerate() {
+            for object in &plugin.objects {
+                if let Some(key) = object.override_key() {
+                    push_version(records.entry(key).or_default(), position, object);
+                }
+            }
+
+            let masters = plugin.header().map_or(&[][..], |header| &header.masters);
+            for cell in plugin.objects_of_type::<Cell>() {
+                for (&(mast_index, refr_index), reference) in &cell.references {
+                    let master = match mast_index {
+                        0 => *name,
+                        _ => match masters.get(mast_index as usize - 1) {
+                            Some((master, _)) => master,
+                            None => continue,
+                        },
+                    };
+                    let key = (master.to_ascii_lowercase(), refr_index);
+                    let (_, versions) = references.entry(key).or_insert_with(|| (master, vec![]));
+                    push_version(versions, position, (cell, reference));
+                }
+            }
+        }
+
+        let mut report = Self::default();
+
+        for versions in records.into_values().filter(|versions| versions.len() > 1) {
+            let (winner, losers) = winner_and_losers(plugins, &versions);
+            let values: Vec<_> = versions.iter().map(|(_, object)| record_value(object)).collect();
+            let object = versions[versions.len() - 1].1;
+            report.records.push(RecordConflict {
+                tag: object.tag_str().to_owned(),
+                id: object.editor_id().into_owned(),
+                winner,
+                losers,
+                fields: conflicting_fields(&values),
+            });
+        }
+
+        for ((_, refr_index), (master, versions)) in references {
+            if versions.len() < 2 {
+                continue;
+            }
+            let (winner, losers) = winner_and_losers(plugins, &versions);
+            let values: Vec<_> = versions
+                .iter()
+                .map(|(_, (_, reference))| reference_value(reference))
+                .collect();
+            let (cell, _) = versions[versions.len() - 1].1;
+            report.references.push(ReferenceConflict {
+                master: master.to_owned(),
+                refr_index,
+                cell: cell.editor_id().into_owned(),
+                winner,
+                losers,
+                fields: conflicting_fields(&values),
+            });
+        }
+
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty() && self.references.is_empty()
+    }
+
+    /// Write the report as text, with a paragraph for each conflict.
+    ///
+    /// ```text
+    /// NPC_ fargoth
+    ///   winner: My Mod.esp
+    ///   losers: Morrowind.esm
+    ///   fields: name, data.level
+    /// ```
+    pub fn save_text(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut write_conflict = |title: String, winner: &str, losers: &[String], fields: &[String]| {
+            writeln!(writer, "{title}")?;
+            writeln!(writer, "  winner: {winner}")?;
+            writeln!(writer, "  losers: {}", losers.join(", "))?;
+            if fields.is_empty() {
+                writeln!(writer, "  fields: none, the versions are identical")?;
+            } else {
+                writeln!(writer, "  fields: {}", fields.join(", "))?;
+            }
+            writeln!(writer)
+        };
+
+        for conflict in &self.records {
+            let title = format!("{} {}", conflict.tag, conflict.id);
+            write_conflict(title, &conflict.winner, &conflict.losers, &conflict.fields)?;
+        }
+
+        for conflict in &self.references {
+            let title = format!("REFR {} {} in {}", conflict.master, conflict.refr_index, conflict.cell);
+            write_conflict(title, &conflict.winner, &conflict.losers, &conflict.fields)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn save_json(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// The names of the plugin whose version wins, and of the others in load order.
+fn winner_and_losers<T>(plugins: &[(&str, &Plugin)], versions: &[(usize, T)]) -> (String, Vec<String>) {
+    let mut names = versions
+        .iter()
+        .map(|(position, _)| plugins[*position].0.to_owned())
+        .collect::<Vec<_>>();
+    let winner = names.pop().unwrap_or_default();
+    (winner, names)
+}
+
+/// Add a version, replacing the previous one if it came from the same plugin, as a plugin
+/// defining something twice does not conflict with itself.
+fn push_version<T>(versions: &mut Versions<T>, position: usize, version: T) {
+    if versions.last().is_some_and(|(last, _)| *last == position) {
+        versions.pop();
+    }
+    versions.push((position, version));
+}
+
+/// The serialized record, without its references and with its id in ascii lowercase, as ids
+/// differing only in case are the same record.
+fn record_value(object: &TES3Object) -> Value {
+    let mut value = serde_json::to_value(object).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.remove("references");
+        if let Some(Value::String(id)) = map.get_mut("id") {
+            id.make_ascii_lowercase();
+        }
+    }
+    value
+}
+
+fn reference_value(reference: &Reference) -> Value {
+    let mut value = serde_json::to_value(reference).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.remove("mast_index");
+    }
+    value
+}
+
+/// The paths of the fields that are not equal in every one of `values`.
+///
+/// Objects are compared field by field, while other values, including lists, are compared whole.
+fn conflicting_fields(values: &[Value]) -> Vec<String> {
+    let values: Vec<_> = values.iter().collect();
+    let mut fields = vec![];
+    add_conflicting_fields("", &values, &mut fields);
+    fields
+}
+
+fn add_conflicting_fields(path: &str, values: &[&Value], fields: &mut Vec<String>) {
+    if values.windows(2).all(|pair| pair[0] == pair[1]) {
+        return;
+    }
+
+    if !values.iter().all(|value| value.is_object()) {
+        fields.push(path.to_owned());
+        return;
+    }
+
+    let mut keys: Vec<&String> = vec![];
+    for map in values.iter().filter_map(|value| value.as_object()) {
+        for key in map.keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    for key in keys {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        let values: Vec<_> = values.iter().map(|value| value.get(key).unwrap_or(&Value::Null)).collect();
+        add_conflicting_fields(&path, &values, fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc(id: &str, name: &str, level: i16) -> TES3Object {
+        let mut npc = Npc {
+            id: id.into(),
+            name: name.into(),
+            ..default()
+        };
+        npc.data.level = level;
+        npc.into()
+    }
+
+    fn cell(references: &[(u32, u32, f32)]) -> TES3Object {
+        let mut cell = Cell {
+            name: "Seyda Neen".into(),
+            ..default()
+        };
+        cell.data.grid = (-2, -9);
+        for &(mast_index, refr_index, x) in references {
+            let reference = Reference {
+                mast_index,
+                refr_index,
+                id: "ex_common_door".into(),
+                translation: [x, 0.0, 0.0],
+                ..default()
+            };
+            cell.references.insert((mast_index, refr_index), reference);
+        }
+        cell.into()
+    }
+
+    #[test]
+    fn conflicts() -> io::Result<()> {
+        let morrowind = Plugin::with_masters(&[], vec![npc("fargoth", "Fargoth", 2), cell(&[(0, 1, 0.0), (0, 2, 0.0)])]);
+        let tribunal = Plugin::with_masters(&[("Morrowind.esm", 0)], vec![npc("barenziah", "Barenziah", 20)]);
+        let mod_a = Plugin::with_masters(
+            &[("Morrowind.esm", 0)],
+            vec![npc("Fargoth", "Fargoth the Brave", 2), cell(&[(1, 1, 10.0), (1, 2, 0.0)])],
+        );
+        let mod_b = Plugin::with_masters(
+            &[("Tribunal.esm", 0), ("Morrowind.esm", 0)],
+            vec![
+                npc("FARGOTH", "Fargoth", 50),
+                npc("barenziah", "Barenziah", 20),
+                cell(&[(2, 1, 20.0)]),
+            ],
+        );
+
+        let report = ConflictReport::new(&[
+            ("Morrowind.esm", &morrowind),
+            ("Tribunal.esm", &tribunal),
+            ("Mod A.esp", &mod_a),
+            ("Mod B.esp", &mod_b),
+        ]);
+
+        let records: Vec<_> = report
+            .records
+            .iter()
+            .map(|conflict| {
+                (
+                    conflict.tag.as_str(),
+                    conflict.id.as_str(),
+                    conflict.winner.as_str(),
+                    conflict.fields.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            records,
+            [
+                ("CELL", "Seyda Neen (-2, -9)", "Mod B.esp", vec![]),
+                ("NPC_", "barenziah", "Mod B.esp", vec![]),
+                (
+                    "NPC_",
+                    "FARGOTH",
+                    "Mod B.esp",
+                    vec!["name".to_owned(), "data.level".to_owned()]
+                ),
+            ]
+        );
+        assert_eq!(report.records[2].losers, ["Morrowind.esm", "Mod A.esp"]);
+
+        // references are matched through each plugin's masters
+        assert_eq!(
+            report.references,
+            [
+                ReferenceConflict {
+                    master: "Morrowind.esm".into(),
+                    refr_index: 1,
+                    cell: "Seyda Neen (-2, -9)".into(),
+                    winner: "Mod B.esp".into(),
+                    losers: vec!["Morrowind.esm".into(), "Mod A.esp".into()],
+                    fields: vec!["translation".into()],
+                },
+                ReferenceConflict {
+                    master: "Morrowind.esm".into(),
+                    refr_index: 2,
+                    cell: "Seyda Neen (-2, -9)".into(),
+                    winner: "Mod A.esp".into(),
+                    losers: vec!["Morrowind.esm".into()],
+                    fields: vec![],
+                },
+            ]
+        );
+
+        let mut text = vec![];
+        report.save_text(&mut text)?;
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.contains(
+            "NPC_ FARGOTH\n  winner: Mod B.esp\n  losers: Morrowind.esm, Mod A.esp\n  fields: name, data.level\n"
+        ));
+        assert!(text.contains("REFR Morrowind.esm 2 in Seyda Neen (-2, -9)\n"));
+
+        let mut json = vec![];
+        report.save_json(&mut json)?;
+        let loaded: ConflictReport = serde_json::from_slice(&json)?;
+        assert_eq!(loaded, report);
+
+        Ok(())
+    }
+}
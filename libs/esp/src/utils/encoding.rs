@@ -0,0 +1,159 @@
+// external imports
+use encoding_rs::{Encoding, WINDOWS_1250, WINDOWS_1251, WINDOWS_1252};
+
+// internal imports
+use crate::prelude::*;
+
+/// The longest id that fits the fixed 32 byte fields used for inventories, spell lists, AI
+/// targets and script names, which must end with a nul byte.
+pub const MAX_ID_LENGTH: usize = 31;
+
+/// The code page used for strings in a plugin.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TextEncoding {
+    /// Central European, used by the Polish release.
+    Windows1250,
+    /// Cyrillic, used by the Russian release.
+    Windows1251,
+    /// Western European, used by the English, German and French releases.
+    #[default]
+    Windows1252,
+}
+
+impl TextEncoding {
+    pub const ALL: [Self; 3] = [Self::Windows1252, Self::Windows1250, Self::Windows1251];
+
+    pub fn encoding(self) -> &'static Encoding {
+        match self {
+            Self::Windows1250 => WINDOWS_1250,
+            Self::Windows1251 => WINDOWS_1251,
+            Self::Windows1252 => WINDOWS_1252,
+        }
+    }
+
+    /// Whether `id` fits in [`MAX_ID_LENGTH`] bytes once encoded.
+    pub fn fits_id_length(self, id: &str) -> bool {
+        self.encoding().encode(id).0.len() <= MAX_ID_LENGTH
+    }
+
+    /// Guess the encoding of a plugin from its raw bytes.
+    ///
+    /// Every subrecord that looks like text is decoded with each candidate encoding, and the
+    /// encoding producing the fewest implausible words wins. Ties favor Windows-1252.
+    pub fn detect(bytes: &[u8]) -> Self {
+        let mut scores = [0usize; 3];
+
+        for text in text_subrecords(bytes) {
+            for (score, encoding) in scores.iter_mut().zip(Self::ALL) {
+                let (decoded, _) = encoding.encoding().decode_without_bom_handling(text);
+                *score += decoded
+                    .split(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+                    .filter(|word| !word.is_ascii() && encoding.is_implausible(word))
+                    .count();
+            }
+        }
+
+        // `min_by_key` keeps the first of equal scores
+        (0..3).min_by_key(|&i| scores[i]).map_or_else(default, |i| Self::ALL[i])
+    }
+
+    /// Whether a decoded word is unlikely to appear in text written for this encoding.
+    fn is_implausible(self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+
+        // symbols in the middle of a word, e.g. "³" in "bia³y"
+        let broken = chars.windows(3).any(|window| {
+            let [before, c, after] = [window[0], window[1], window[2]];
+            !c.is_ascii() && !c.is_alphabetic() && before.is_alphabetic() && after.is_alphabetic()
+        });
+        if broken {
+            return true;
+        }
+
+        let letters = chars.iter().filter(|c| c.is_alphabetic());
+        let (ascii, other) = letters.fold((0, 0), |(ascii, other), c| {
+            if c.is_ascii() {
+                (ascii + 1, other)
+            } else {
+                (ascii, other + 1)
+            }
+        });
+
+        match self {
+            // latin words are never spelled entirely with accented letters
+            Self::Windows1250 | Self::Windows1252 => ascii == 0 && other >= 3,
+            // cyrillic words are never spelled with latin letters
+            Self::Windows1251 => ascii > 0 && other > 0,
+        }
+    }
+}
+
+/// The payloads of every subrecord that appears to be a string and contains non-ASCII bytes.
+fn text_subrecords(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let read_u32 = |bytes: &[u8], offset: usize| -> Option<usize> {
+        let value = bytes.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes(value.try_into().ok()?) as usize)
+    };
+
+    let mut records = vec![];
+    let mut offset = 0;
+    while let Some(size) = read_u32(bytes, offset + 4) {
+        let Some(record) = bytes.get(offset + 16..offset + 16 + size) else {
+            break;
+        };
+        records.push(record);
+        offset += 16 + size;
+    }
+
+    records.into_iter().flat_map(move |record| {
+        let mut subrecords = vec![];
+        let mut offset = 0;
+        while let Some(size) = read_u32(record, offset + 4) {
+            let Some(data) = record.get(offset + 8..offset + 8 + size) else {
+                break;
+            };
+            let data = data.strip_suffix(b"\0").unwrap_or(data);
+            let is_text = data.iter().all(|&b| b >= 0x20 || matches!(b, b'\t' | b'\n' | b'\r'));
+            if is_text && !data.is_ascii() {
+                subrecords.push(data);
+            }
+            offset += 8 + size;
+        }
+        subrecords
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_bytes(text: &str, encoding: TextEncoding) -> Vec<u8> {
+        let (text, _, _) = encoding.encoding().encode(text);
+        let mut record = vec![];
+        record.extend(b"TEXT");
+        record.extend(u32::try_from(text.len()).unwrap().to_le_bytes());
+        record.extend(text.as_ref());
+
+        let mut bytes = vec![];
+        bytes.extend(b"BOOK");
+        bytes.extend(u32::try_from(record.len()).unwrap().to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes.extend(record);
+        bytes
+    }
+
+    #[test]
+    fn detect() {
+        let samples = [
+            ("Der Bürger trägt einen großen Hut.", TextEncoding::Windows1252),
+            ("Le château est très élevé, à côté du marché.", TextEncoding::Windows1252),
+            ("Zażółć gęślą jaźń, powiedział biały rycerz.", TextEncoding::Windows1250),
+            ("Добро пожаловать в Балмору, чужеземец.", TextEncoding::Windows1251),
+            ("Plain ASCII text.", TextEncoding::Windows1252),
+        ];
+        for (text, expected) in samples {
+            assert_eq!(TextEncoding::detect(&plugin_bytes(text, expected)), expected, "{text}");
+        }
+    }
+}
@@ -0,0 +1,404 @@
+// rust std imports
+use std::io::{BufRead, Write};
+
+// internal imports
+use crate::prelude::*;
+use crate::utils::csv::{csv_quote, csv_rows};
+
+/// The values a game setting is given by the game's masters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameSettingDefault {
+    /// The id, as written by the master that introduced the setting.
+    pub id: String,
+    /// Each master that introduced or changed the setting, with the value it gave, in load order.
+    pub values: Vec<(String, GameSettingValue)>,
+}
+
+impl GameSettingDefault {
+    /// The value the game uses when no plugin changes the setting.
+    pub fn value(&self) -> &GameSettingValue {
+        &self.values[self.values.len() - 1].1
+    }
+
+    /// The master that introduced the setting.
+    pub fn introduced_by(&self) -> &str {
+        &self.values[0].0
+    }
+}
+
+/// The default value of every game setting, as given by the game's masters.
+///
+/// [`GameSettingDefaults::vanilla`] provides a built-in table, while
+/// [`GameSettingDefaults::from_masters`] builds the table from the masters themselves, normally
+/// `Morrowind.esm`, `Tribunal.esm` and `Bloodmoon.esm`, so that it matches the installed version
+/// of the game exactly. Tables can be kept as CSV, see [`GameSettingDefaults::save_csv`].
+///
+/// ```ignore
+/// let removed = plugin.remove_evil_game_settings(&GameSettingDefaults::vanilla());
+///
+/// let defaults = GameSettingDefaults::from_masters(&[
+///     ("Morrowind.esm", &morrowind),
+///     ("Tribunal.esm", &tribunal),
+///     ("Bloodmoon.esm", &bloodmoon),
+/// ]);
+/// let removed = plugin.remove_evil_game_settings(&defaults);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameSettingDefaults {
+    /// The masters the settings come from, in load order. The first is the base game.
+    masters: Vec<String>,
+    /// The settings, by lowercase id.
+    settings: HashMap<String, GameSettingDefault>,
+}
+
+impl GameSettingDefaults {
+    /// The built-in settings of `Morrowind.esm`, `Tribunal.esm` and `Bloodmoon.esm`, read from
+    /// `game_settings.csv` next to this module.
+    ///
+    /// Holds every setting added by the expansions, which is what [`GameSettingDefaults::is_evil`]
+    /// needs to find settings that depend on a missing expansion, and a selection of the settings
+    /// of the base game. The table is written by [`GameSettingDefaults::save_csv`], so it can be
+    /// regenerated from the masters, see the `generate_vanilla_game_settings` test. Use
+    /// [`GameSettingDefaults::from_masters`] for the complete table of an installed game.
+    pub fn vanilla() -> Self {
+        Self::load_csv(VANILLA_GAME_SETTINGS.as_bytes()).unwrap_or_default()
+    }
+
+    /// The settings of `masters`, given with their file names in load order. The first master is
+    /// taken to be the base game.
+    pub fn from_masters(masters: &[(&str, &Plugin)]) -> Self {
+        let mut this = Self {
+            masters: masters.iter().map(|(name, _)| (*name).to_owned()).collect(),
+            settings: default(),
+        };
+        for (name, master) in masters {
+            for setting in master.objects_of_type::<GameSetting>().filter(|setting| !setting.deleted()) {
+                this.push(name, &setting.id, setting.value.clone());
+            }
+        }
+        this
+    }
+
+    /// Read settings from CSV with `master,id,type,value` columns, where type is `float`,
+    /// `integer` or `string`. Rows are in load order, with a row for each master that introduced
+    /// or changed a setting.
+    pub fn load_csv(mut reader: impl BufRead) -> io::Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let mut this = Self::default();
+        for (i, row) in csv_rows(&text)?.into_iter().enumerate().skip(1) {
+            if row.iter().all(String::is_empty) {
+                continue;
+            }
+            let [master, id, kind, value] = <[String; 4]>::try_from(row)
+                .or_else(|_| Reader::error(format!("Expected 4 columns on CSV row {}", i + 1)))?;
+            let parsed = match kind.as_str() {
+                "float" => value.parse().ok().map(GameSettingValue::Float),
+                "integer" => value.parse().ok().map(GameSettingValue::Integer),
+                "string" => Some(GameSettingValue::String(value.clone())),
+                _ => return Reader::error(format!("Unknown setting type on CSV row {}: {kind}", i + 1)),
+            };
+            let Some(value) = parsed else {
+                return Reader::error(format!("Invalid {kind} on CSV row {}: {value}", i + 1));
+            };
+            if !this.masters.contains(&master) {
+                this.masters.push(master.clone());
+            }
+            this.push(&master, &id, value);
+        }
+
+        Ok(this)
+    }
+
+    /// Write settings as CSV with `master,id,type,value` columns, as read by
+    /// [`GameSettingDefaults::load_csv`]. Rows are grouped by master in load order, and sorted by
+    /// id within each master.
+    pub fn save_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut settings: Vec<_> = self.settings.iter().collect();
+        settings.sort_unstable_by_key(|(key, _)| *key);
+
+        writeln!(writer, "master,id,type,value")?;
+        for master in &self.masters {
+            for (_, setting) in &settings {
+                for (_, value) in setting.values.iter().filter(|(other, _)| other == master) {
+                    let (kind, value) = match value {
+                        GameSettingValue::Float(value) => ("float", value.to_string()),
+                        GameSettingValue::Integer(value) => ("integer", value.to_string()),
+                        GameSettingValue::String(value) => ("string", value.clone()),
+                    };
+                    writeln!(writer, "{master},{},{kind},{}", csv_quote(&setting.id), csv_quote(&value))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the value `master` gives a setting, if it differs from the previous one.
+    fn push(&mut self, master: &str, id: &str, value: GameSettingValue) {
+        let default = self
+            .settings
+            .entry(id.to_ascii_lowercase())
+            .or_insert_with(|| GameSettingDefault {
+                id: id.to_owned(),
+                values: vec![],
+            });
+        if default.values.last().is_none_or(|(_, last)| *last != value) {
+            default.values.push((master.to_owned(), value));
+        }
+    }
+
+    /// The setting with the given id, ignoring ascii case.
+    pub fn get(&self, id: &str) -> Option<&GameSettingDefault> {
+        self.settings.get(&id.to_ascii_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.settings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// Whether `setting`, found in a plugin with the given masters, looks like one generated by
+    /// the Construction Set rather than changed on purpose. These are known as "evil" GMSTs.
+    ///
+    /// A setting is evil if it restores the value given by an earlier master, undoing the change
+    /// of a later master such as an expansion, or if it was introduced by an expansion that is not
+    /// one of the plugin's masters.
+    pub fn is_evil(&self, setting: &GameSetting, masters: &[(String, u64)]) -> bool {
+        let Some(default) = self.get(&setting.id) else {
+            return false;
+        };
+
+        let introduced_by = default.introduced_by();
+        let base = self.masters.first().map_or("", String::as_str);
+        if !introduced_by.eq_ignore_ascii_case(base)
+            && !masters.iter().any(|(master, _)| master.eq_ignore_ascii_case(introduced_by))
+        {
+            return true;
+        }
+
+        let (earlier, _) = default.values.split_at(default.values.len() - 1);
+        setting.value != *default.value() && earlier.iter().any(|(_, value)| *value == setting.value)
+    }
+}
+
+impl Plugin {
+    /// The value of the game setting with the given id, ignoring ascii case.
+    ///
+    /// The value from this plugin is used if it has the setting, otherwise the default.
+    pub fn game_setting<'a>(&'a self, id: &str, defaults: &'a GameSettingDefaults) -> Option<&'a GameSettingValue> {
+        match self.get::<GameSetting>(id) {
+            Some(setting) if !setting.deleted() => Some(&setting.value),
+            _ => defaults.get(id).map(GameSettingDefault::value),
+        }
+    }
+
+    /// As [`Plugin::game_setting`], for settings whose value is a float.
+    pub fn game_setting_float(&self, id: &str, defaults: &GameSettingDefaults) -> Option<f32> {
+        match self.game_setting(id, defaults)? {
+            GameSettingValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// As [`Plugin::game_setting`], for settings whose value is an integer.
+    pub fn game_setting_integer(&self, id: &str, defaults: &GameSettingDefaults) -> Option<i32> {
+        match self.game_setting(id, defaults)? {
+            GameSettingValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// As [`Plugin::game_setting`], for settings whose value is a string.
+    pub fn game_setting_string<'a>(&'a self, id: &str, defaults: &'a GameSettingDefaults) -> Option<&'a str> {
+        match self.game_setting(id, defaults)? {
+            GameSettingValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The game settings of this plugin that are evil, see [`GameSettingDefaults::is_evil`].
+    pub fn evil_game_settings<'a>(&'a self, defaults: &GameSettingDefaults) -> Vec<&'a GameSetting> {
+        let masters = self.header().map_or(&[][..], |header| &header.masters);
+        self.objects_of_type::<GameSetting>()
+            .filter(|setting| defaults.is_evil(setting, masters))
+            .collect()
+    }
+
+    /// Remove the evil game settings of this plugin, see [`GameSettingDefaults::is_evil`].
+    /// Returns the removed settings.
+    pub fn remove_evil_game_settings(&mut self, defaults: &GameSettingDefaults) -> Vec<GameSetting> {
+        let masters = self.header().map(|header| header.masters.clone()).unwrap_or_default();
+
+        let mut removed = vec![];
+        self.objects.retain(|object| match object {
+            TES3Object::GameSetting(setting) if defaults.is_evil(setting, &masters) => {
+                removed.push(setting.clone());
+                false
+            }
+            _ => true,
+        });
+
+        if !removed.is_empty() && self.index.is_some() {
+            self.build_index();
+        }
+
+        removed
+    }
+}
+
+/// The settings of the vanilla masters, see [`GameSettingDefaults::vanilla`].
+const VANILLA_GAME_SETTINGS: &str = include_str!("game_settings.csv");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+
+    fn settings(settings: &[(&str, GameSettingValue)]) -> Vec<TES3Object> {
+        (settings.iter())
+            .map(|(id, value)| {
+                GameSetting {
+                    id: (*id).to_owned(),
+                    value: value.clone(),
+                    ..default()
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evil_game_settings() {
+        use GameSettingValue::{Float, Integer, String};
+
+        let morrowind = Plugin::with_masters(
+            &[],
+            settings(&[
+                ("fCombatDistance", Float(128.0)),
+                ("fFleeDistance", Float(3000.0)),
+                ("sWerewolfPopup", String("Werewolf".into())),
+            ]),
+        );
+        let tribunal = Plugin::with_masters(&[("Morrowind.esm", 0)], settings(&[("fFleeDistance", Float(2000.0))]));
+        let bloodmoon = Plugin::with_masters(
+            &[("Morrowind.esm", 0), ("Tribunal.esm", 0)],
+            settings(&[("iWereWolfBounty", Integer(10000)), ("fFleeDistance", Float(2000.0))]),
+        );
+        let defaults = GameSettingDefaults::from_masters(&[
+            ("Morrowind.esm", &morrowind),
+            ("Tribunal.esm", &tribunal),
+            ("Bloodmoon.esm", &bloodmoon),
+        ]);
+        assert_eq!(defaults.len(), 4);
+        assert_eq!(defaults.get("ffleedistance").unwrap().values.len(), 2);
+        assert_eq!(defaults.get("IWEREWOLFBOUNTY").unwrap().introduced_by(), "Bloodmoon.esm");
+
+        // a setting changed by an expansion keeps a row for each master
+        let mut csv = vec![];
+        defaults.save_csv(&mut csv).unwrap();
+        let csv = std::string::String::from_utf8(csv).unwrap();
+        assert!(csv.contains("Morrowind.esm,fFleeDistance,float,3000\n"));
+        assert!(csv.contains("Tribunal.esm,fFleeDistance,float,2000\n"));
+        assert_eq!(GameSettingDefaults::load_csv(csv.as_bytes()).unwrap(), defaults);
+
+        let mut plugin = Plugin::with_masters(
+            &[("Morrowind.esm", 0)],
+            settings(&[
+                ("fFleeDistance", Float(3000.0)),
+                ("iWereWolfBounty", Integer(10000)),
+                ("fCombatDistance", Float(256.0)),
+            ]),
+        );
+        let evil: Vec<_> = plugin
+            .evil_game_settings(&defaults)
+            .iter()
+            .map(|setting| setting.id.as_str())
+            .collect();
+        assert_eq!(evil, ["fFleeDistance", "iWereWolfBounty"]);
+
+        // settings of the expansions are not evil when the plugin depends on them
+        let mut expansion_plugin = Plugin::with_masters(
+            &[("Morrowind.esm", 0), ("Tribunal.esm", 0), ("Bloodmoon.esm", 0)],
+            settings(&[("iWereWolfBounty", Integer(500)), ("fFleeDistance", Float(2500.0))]),
+        );
+        assert!(expansion_plugin.evil_game_settings(&defaults).is_empty());
+        assert!(expansion_plugin.remove_evil_game_settings(&defaults).is_empty());
+
+        assert_eq!(plugin.game_setting_float("fcombatdistance", &defaults), Some(256.0));
+        assert_eq!(plugin.remove_evil_game_settings(&defaults).len(), 2);
+        assert_eq!(plugin.game_setting_float("fFleeDistance", &defaults), Some(2000.0));
+        assert_eq!(plugin.game_setting_integer("iWereWolfBounty", &defaults), Some(10000));
+        assert_eq!(plugin.game_setting_string("sWerewolfPopup", &defaults), Some("Werewolf"));
+        assert_eq!(plugin.game_setting_string("fFleeDistance", &defaults), None);
+        assert_eq!(plugin.game_setting("sMissing", &defaults), None);
+    }
+
+    #[test]
+    fn vanilla_game_settings() {
+        use GameSettingValue::{Float, Integer, String};
+
+        let defaults = GameSettingDefaults::vanilla();
+        assert_eq!(defaults.len(), 82);
+        // the built-in table is kept exactly as generated, so that regenerating it is a clean diff
+        let mut csv = vec![];
+        defaults.save_csv(&mut csv).unwrap();
+        assert_eq!(std::str::from_utf8(&csv), Ok(VANILLA_GAME_SETTINGS));
+
+        let combat_distance = defaults.get("fcombatdistance").unwrap();
+        assert_eq!(combat_distance.id, "fCombatDistance");
+        assert_eq!(*combat_distance.value(), Float(128.0));
+        assert_eq!(combat_distance.introduced_by(), "Morrowind.esm");
+        assert_eq!(defaults.get("sCompanionShare").unwrap().introduced_by(), "Tribunal.esm");
+        assert_eq!(*defaults.get("iWereWolfBounty").unwrap().value(), Integer(1000));
+        assert_eq!(defaults.get("iWereWolfBounty").unwrap().introduced_by(), "Bloodmoon.esm");
+
+        let plugin = Plugin::with_masters(
+            &[("Morrowind.esm", 0), ("Tribunal.esm", 0)],
+            settings(&[
+                ("iMaxActivateDist", Integer(256)),
+                ("sProfitValue", String("Profit Value".into())),
+                ("sWerewolfPopup", String("Werewolf".into())),
+            ]),
+        );
+        let evil: Vec<_> = plugin
+            .evil_game_settings(&defaults)
+            .iter()
+            .map(|setting| setting.id.as_str())
+            .collect();
+        assert_eq!(evil, ["sWerewolfPopup"]);
+        assert_eq!(plugin.game_setting_integer("iMaxActivateDist", &defaults), Some(256));
+        assert_eq!(plugin.game_setting_float("fWereWolfRunMult", &defaults), Some(1.3));
+    }
+
+    /// Regenerate `game_settings.csv` from the masters in the directory named by the
+    /// `MORROWIND_DATA_FILES` environment variable, with
+    /// `cargo test -p esp generate_vanilla_game_settings -- --ignored`.
+    #[test]
+    #[ignore = "needs the game's masters"]
+    fn generate_vanilla_game_settings() -> io::Result<()> {
+        let data_files = std::env::var("MORROWIND_DATA_FILES")
+            .map_err(|_| io::Error::other("MORROWIND_DATA_FILES must name the Data Files directory"))?;
+        let names = ["Morrowind.esm", "Tribunal.esm", "Bloodmoon.esm"];
+        let mut masters = vec![];
+        for name in names {
+            let mut master = Plugin::new();
+            master.load_path_filtered(Path::new(&data_files).join(name), |tag| tag == *GameSetting::TAG)?;
+            masters.push(master);
+        }
+        let masters: Vec<_> = names.into_iter().zip(&masters).collect();
+        let defaults = GameSettingDefaults::from_masters(&masters);
+
+        // every setting of the base game, and some changed by the expansions
+        assert!(defaults.len() > 1400);
+        assert!(defaults.settings.values().any(|setting| setting.values.len() > 1));
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/utils/game_settings.csv");
+        defaults.save_csv(std::fs::File::create(path)?)
+    }
+}
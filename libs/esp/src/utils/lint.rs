@@ -0,0 +1,621 @@
+// rust std imports
+use std::fmt;
+
+// internal imports
+use crate::prelude::*;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The record works, but probably not as intended.
+    Warning,
+    /// The record cannot be saved, or the game will misbehave when loading it.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found by a [`Lint`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// The name of the lint that found the problem, filled in by [`LintRegistry`].
+    pub lint: &'static str,
+    pub severity: Severity,
+    /// The file name of the plugin, when linting a load order.
+    pub plugin: Option<String>,
+    pub tag: [u8; 4],
+    pub id: String,
+    /// The field of the record with the problem, if any.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, object: &(impl TypeInfo + EditorId), message: impl Into<String>) -> Self {
+        Self {
+            lint: "",
+            severity,
+            plugin: None,
+            tag: *object.tag(),
+            id: object.editor_id().into_owned(),
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    pub fn tag_str(&self) -> &str {
+        std::str::from_utf8(&self.tag).unwrap_or("????")
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: ", self.severity, self.lint)?;
+        if let Some(plugin) = &self.plugin {
+            write!(f, "{plugin}: ")?;
+        }
+        write!(f, "{} '{}'", self.tag_str(), self.id)?;
+        if let Some(field) = &self.field {
+            write!(f, " {field}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// A rule that checks plugins for problems.
+///
+/// ```ignore
+/// struct NoScripts;
+///
+/// impl Lint for NoScripts {
+///     fn name(&self) -> &'static str {
+///         "no-scripts"
+///     }
+///
+///     fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+///         for script in plugin.objects_of_type::<Script>() {
+///             diagnostics.push(Diagnostic::new(Severity::Warning, script, "scripts are not allowed"));
+///         }
+///     }
+/// }
+///
+/// let mut registry = LintRegistry::new();
+/// registry.register(NoScripts);
+/// ```
+pub trait Lint {
+    /// A short name identifying the rule, such as `"id-length"`.
+    fn name(&self) -> &'static str;
+
+    /// Check the records of `plugin`.
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>);
+
+    /// Check the plugins of a load order, given with their file names in load order.
+    ///
+    /// By default each plugin is checked separately.
+    fn check_load_order(&self, plugins: &[(&str, &Plugin)], diagnostics: &mut Vec<Diagnostic>) {
+        for (name, plugin) in plugins {
+            let start = diagnostics.len();
+            self.check(plugin, diagnostics);
+            for diagnostic in &mut diagnostics[start..] {
+                diagnostic.plugin.get_or_insert_with(|| (*name).to_owned());
+            }
+        }
+    }
+}
+
+/// A set of [`Lint`] rules to run together.
+pub struct LintRegistry {
+    lints: Vec<Box<dyn Lint>>,
+}
+
+impl Default for LintRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for LintRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.lints.iter().map(|lint| lint.name())).finish()
+    }
+}
+
+impl LintRegistry {
+    /// A registry with the built-in rules.
+    pub fn new() -> Self {
+        let mut this = Self::empty();
+        this.register(IdLength)
+            .register(ScaleRange)
+            .register(WeatherChancesTotal)
+            .register(DuplicateIds)
+            .register(EmptyItemNames)
+            .register(AiPackageParameters);
+        this
+    }
+
+    /// A registry without any rules.
+    pub fn empty() -> Self {
+        Self { lints: vec![] }
+    }
+
+    /// Add a rule, replacing any rule with the same name.
+    pub fn register(&mut self, lint: impl Lint + 'static) -> &mut Self {
+        self.lints.retain(|other| other.name() != lint.name());
+        self.lints.push(Box::new(lint));
+        self
+    }
+
+    /// Remove the rule with the given name, returning whether there was one.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let len = self.lints.len();
+        self.lints.retain(|lint| lint.name() != name);
+        self.lints.len() != len
+    }
+
+    pub fn lints(&self) -> impl Iterator<Item = &dyn Lint> {
+        self.lints.iter().map(AsRef::as_ref)
+    }
+
+    /// Run every rule over `plugin`.
+    pub fn check(&self, plugin: &Plugin) -> Vec<Diagnostic> {
+        self.run(|lint, diagnostics| lint.check(plugin, diagnostics))
+    }
+
+    /// Run every rule over the plugins of a load order, given with their file names in load order.
+    pub fn check_load_order(&self, plugins: &[(&str, &Plugin)]) -> Vec<Diagnostic> {
+        self.run(|lint, diagnostics| lint.check_load_order(plugins, diagnostics))
+    }
+
+    fn run(&self, mut check: impl FnMut(&dyn Lint, &mut Vec<Diagnostic>)) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for lint in self.lints() {
+            let start = diagnostics.len();
+            check(lint, &mut diagnostics);
+            for diagnostic in &mut diagnostics[start..] {
+                diagnostic.lint = lint.name();
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Ids longer than fit the fixed 32 byte fields they are stored in or referenced from.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdLength;
+
+impl Lint for IdLength {
+    fn name(&self) -> &'static str {
+        "id-length"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        let too_long = |id: &str| !plugin.encoding().fits_id_length(id);
+        let message = |id: &str| format!("'{id}' is longer than {MAX_ID_LENGTH} bytes");
+
+        for object in &plugin.objects {
+            let id = object.editor_id();
+            let severity = match object {
+                // these ids are never stored in fixed length fields
+                TES3Object::Header(_)
+                | TES3Object::GameSetting(_)
+                | TES3Object::Cell(_)
+                | TES3Object::Landscape(_)
+                | TES3Object::PathGrid(_)
+                | TES3Object::Dialogue(_)
+                | TES3Object::DialogueInfo(_)
+                | TES3Object::StartScript(_) => continue,
+                // the id of a script is itself saved as a fixed length field
+                TES3Object::Script(_) => Severity::Error,
+                _ => Severity::Warning,
+            };
+            if too_long(&id) {
+                let message = format!("{}, so it cannot be referenced from other records", message(&id));
+                diagnostics.push(Diagnostic::new(severity, object, message).with_field("id"));
+            }
+
+            let mut fields: Vec<(&str, &str)> = vec![];
+            match object {
+                TES3Object::Npc(npc) => {
+                    fields.extend(npc.inventory.iter().map(|(_, id)| ("inventory", id.as_str())));
+                    fields.extend(npc.spells.iter().map(|id| ("spells", id.as_str())));
+                }
+                TES3Object::Creature(creature) => {
+                    fields.extend(creature.inventory.iter().map(|(_, id)| ("inventory", id.as_str())));
+                    fields.extend(creature.spells.iter().map(|id| ("spells", id.as_str())));
+                }
+                TES3Object::Container(container) => {
+                    fields.extend(container.inventory.iter().map(|(_, id)| ("inventory", id.as_str())));
+                }
+                TES3Object::Birthsign(birthsign) => {
+                    fields.extend(birthsign.spells.iter().map(|id| ("spells", id.as_str())));
+                }
+                TES3Object::Race(race) => {
+                    fields.extend(race.spells.iter().map(|id| ("spells", id.as_str())));
+                }
+                TES3Object::Region(region) => {
+                    fields.extend(region.sounds.iter().map(|(id, _)| ("sounds", id.as_str())));
+                }
+                _ => {}
+            }
+            for (field, id) in fields.into_iter().filter(|(_, id)| too_long(id)) {
+                diagnostics.push(Diagnostic::new(Severity::Error, object, message(id)).with_field(field));
+            }
+        }
+    }
+}
+
+/// Scales outside the range the game supports, which are clamped when saved.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScaleRange;
+
+impl Lint for ScaleRange {
+    fn name(&self) -> &'static str {
+        "scale-range"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        let invalid = |scale: Option<f32>| scale.filter(|scale| !(0.5..=2.0).contains(scale));
+        let message = |scale: f32| format!("{scale} is outside 0.5 to 2.0 and will be clamped");
+
+        for cell in plugin.objects_of_type::<Cell>() {
+            for reference in cell.references.values() {
+                if let Some(scale) = invalid(reference.scale) {
+                    let message = format!("reference {} of '{}': {}", reference.refr_index, reference.id, message(scale));
+                    diagnostics.push(Diagnostic::new(Severity::Warning, cell, message).with_field("scale"));
+                }
+            }
+        }
+        for creature in plugin.objects_of_type::<Creature>() {
+            if let Some(scale) = invalid(creature.scale) {
+                diagnostics.push(Diagnostic::new(Severity::Warning, creature, message(scale)).with_field("scale"));
+            }
+        }
+    }
+}
+
+/// Regions whose weather chances do not add up to 100.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WeatherChancesTotal;
+
+impl Lint for WeatherChancesTotal {
+    fn name(&self) -> &'static str {
+        "weather-chances"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        for region in plugin.objects_of_type::<Region>().filter(|region| !region.deleted()) {
+            let chances = &region.weather_chances;
+            let total: u32 = [
+                chances.clear,
+                chances.cloudy,
+                chances.foggy,
+                chances.overcast,
+                chances.rain,
+                chances.thunder,
+                chances.ash,
+                chances.blight,
+                chances.snow,
+                chances.blizzard,
+            ]
+            .into_iter()
+            .map(u32::from)
+            .sum();
+            if total != 100 {
+                let message = format!("chances add up to {total} instead of 100");
+                diagnostics.push(Diagnostic::new(Severity::Warning, region, message).with_field("weather_chances"));
+            }
+        }
+    }
+}
+
+/// Records of the same type defined more than once in a plugin, of which the game uses only the
+/// last.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DuplicateIds;
+
+impl Lint for DuplicateIds {
+    fn name(&self) -> &'static str {
+        "duplicate-ids"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        let mut seen = HashSet::new();
+        for object in &plugin.objects {
+            // these are identified by more than their id
+            if matches!(
+                object,
+                TES3Object::Header(_) | TES3Object::Cell(_) | TES3Object::Landscape(_) | TES3Object::PathGrid(_)
+            ) {
+                continue;
+            }
+            let id = object.editor_id_ascii_lowercase();
+            if !id.is_empty() && !seen.insert((*object.tag(), id.into_owned())) {
+                let message = format!("{} is defined more than once", object.type_name());
+                diagnostics.push(Diagnostic::new(Severity::Error, object, message).with_field("id"));
+            }
+        }
+    }
+}
+
+/// Items that can be picked up but have no name, so cannot be seen or activated in game.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmptyItemNames;
+
+impl Lint for EmptyItemNames {
+    fn name(&self) -> &'static str {
+        "empty-item-names"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        for object in &plugin.objects {
+            let name = match object {
+                TES3Object::Alchemy(item) => &item.name,
+                TES3Object::Apparatus(item) => &item.name,
+                TES3Object::Armor(item) => &item.name,
+                TES3Object::Book(item) => &item.name,
+                TES3Object::Clothing(item) => &item.name,
+                TES3Object::Ingredient(item) => &item.name,
+                TES3Object::Light(item) if item.data.flags.contains(LightFlags::CAN_CARRY) => &item.name,
+                TES3Object::Lockpick(item) => &item.name,
+                TES3Object::MiscItem(item) => &item.name,
+                TES3Object::Probe(item) => &item.name,
+                TES3Object::RepairItem(item) => &item.name,
+                TES3Object::Weapon(item) => &item.name,
+                _ => continue,
+            };
+            if name.trim().is_empty() && !object.deleted() {
+                diagnostics.push(Diagnostic::new(Severity::Warning, object, "item has no name").with_field("name"));
+            }
+        }
+    }
+}
+
+/// AI packages with parameters the game cannot use.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AiPackageParameters;
+
+impl AiPackageParameters {
+    fn check_package(package: &AiPackage, encoding: TextEncoding) -> Vec<(Severity, String)> {
+        let mut problems = vec![];
+        let location = |location: &[f32; 3], problems: &mut Vec<_>| {
+            if !location.iter().all(|value| value.is_finite()) {
+                problems.push((Severity::Error, format!("location {location:?} is not finite")));
+            }
+        };
+        let target = |target: &str, problems: &mut Vec<_>| {
+            if target.trim().is_empty() {
+                problems.push((Severity::Error, "target is empty".to_owned()));
+            } else if !encoding.fits_id_length(target) {
+                problems.push((
+                    Severity::Error,
+                    format!("target '{target}' is longer than {MAX_ID_LENGTH} bytes"),
+                ));
+            }
+        };
+
+        match package {
+            AiPackage::Travel(package) => location(&package.location, &mut problems),
+            AiPackage::Wander(package) => {
+                if package.game_hour > 24 {
+                    problems.push((Severity::Warning, format!("game hour {} is after 24", package.game_hour)));
+                }
+                let idles = [
+                    package.idle2,
+                    package.idle3,
+                    package.idle4,
+                    package.idle5,
+                    package.idle6,
+                    package.idle7,
+                    package.idle8,
+                    package.idle9,
+                ];
+                for (number, idle) in (2..).zip(idles) {
+                    if idle > 100 {
+                        problems.push((Severity::Warning, format!("idle{number} chance {idle} is above 100")));
+                    }
+                }
+            }
+            AiPackage::Escort(AiEscortPackage {
+                location: position,
+                target: id,
+                ..
+            })
+            | AiPackage::Follow(AiFollowPackage {
+                location: position,
+                target: id,
+                ..
+            }) => {
+                location(position, &mut problems);
+                target(id, &mut problems);
+            }
+            AiPackage::Activate(package) => target(&package.target, &mut problems),
+        }
+        problems
+    }
+}
+
+impl Lint for AiPackageParameters {
+    fn name(&self) -> &'static str {
+        "ai-packages"
+    }
+
+    fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+        for object in &plugin.objects {
+            let packages = match object {
+                TES3Object::Npc(npc) => &npc.ai_packages,
+                TES3Object::Creature(creature) => &creature.ai_packages,
+                _ => continue,
+            };
+            for (index, package) in packages.iter().enumerate() {
+                for (severity, message) in Self::check_package(package, plugin.encoding()) {
+                    let diagnostic = Diagnostic::new(severity, object, message);
+                    diagnostics.push(diagnostic.with_field(format!("ai_packages[{index}]")));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(diagnostics: &[Diagnostic]) -> Vec<(&str, &str, Option<&str>)> {
+        (diagnostics.iter())
+            .map(|diagnostic| (diagnostic.lint, diagnostic.id.as_str(), diagnostic.field.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn built_in_lints() {
+        let long_id = "a_very_long_id_for_a_miscellaneous_item";
+
+        let mut cell = Cell {
+            name: "Balmora".into(),
+            ..default()
+        };
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        for (refr_index, scale) in [(1, Some(1.0)), (2, Some(2.5)), (3, None)] {
+            let reference = Reference {
+                refr_index,
+                id: "ex_common_door".into(),
+                scale,
+                ..default()
+            };
+            cell.references.insert((0, refr_index), reference);
+        }
+
+        let region = Region {
+            id: "Bitter Coast Region".into(),
+            weather_chances: WeatherChances {
+                clear: 50,
+                cloudy: 40,
+                ..default()
+            },
+            ..default()
+        };
+
+        let npc = Npc {
+            id: "fargoth".into(),
+            name: "Fargoth".into(),
+            inventory: vec![(1, long_id.to_owned().into())],
+            ai_packages: vec![
+                AiPackage::Wander(AiWanderPackage { idle3: 150, ..default() }),
+                AiPackage::Follow(default()),
+            ],
+            ..default()
+        };
+
+        let plugin = Plugin::with_masters(
+            &[],
+            vec![
+                MiscItem {
+                    id: long_id.into(),
+                    name: "Item".into(),
+                    ..default()
+                }
+                .into(),
+                MiscItem {
+                    id: "misc_unnamed".into(),
+                    ..default()
+                }
+                .into(),
+                MiscItem {
+                    id: "MISC_UNNAMED".into(),
+                    name: "Unnamed".into(),
+                    ..default()
+                }
+                .into(),
+                Light {
+                    id: "light_unnamed".into(),
+                    ..default()
+                }
+                .into(),
+                // ids are measured in the plugin's encoding, where accented letters take a single byte
+                MiscItem {
+                    id: "é".repeat(MAX_ID_LENGTH),
+                    name: "Accented".into(),
+                    ..default()
+                }
+                .into(),
+                cell.into(),
+                region.into(),
+                npc.into(),
+            ],
+        );
+
+        let diagnostics = LintRegistry::new().check(&plugin);
+        assert_eq!(
+            lints(&diagnostics),
+            [
+                ("id-length", long_id, Some("id")),
+                ("id-length", "fargoth", Some("inventory")),
+                ("scale-range", "Balmora", Some("scale")),
+                ("weather-chances", "Bitter Coast Region", Some("weather_chances")),
+                ("duplicate-ids", "MISC_UNNAMED", Some("id")),
+                ("empty-item-names", "misc_unnamed", Some("name")),
+                ("ai-packages", "fargoth", Some("ai_packages[0]")),
+                ("ai-packages", "fargoth", Some("ai_packages[1]")),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(
+            diagnostics[3].to_string(),
+            "warning[weather-chances]: REGN 'Bitter Coast Region' weather_chances: chances add up to 90 instead of 100"
+        );
+    }
+
+    #[test]
+    fn custom_lints() {
+        struct NoScripts;
+
+        impl Lint for NoScripts {
+            fn name(&self) -> &'static str {
+                "no-scripts"
+            }
+
+            fn check(&self, plugin: &Plugin, diagnostics: &mut Vec<Diagnostic>) {
+                for script in plugin.objects_of_type::<Script>() {
+                    diagnostics.push(Diagnostic::new(Severity::Error, script, "scripts are not allowed"));
+                }
+            }
+        }
+
+        let mut registry = LintRegistry::empty();
+        registry.register(NoScripts).register(DuplicateIds);
+        assert!(registry.unregister("duplicate-ids"));
+        assert!(!registry.unregister("duplicate-ids"));
+        assert_eq!(registry.lints().count(), 1);
+
+        let with_script = Plugin::with_masters(
+            &[],
+            vec![Script {
+                id: "MyScript".into(),
+                ..default()
+            }
+            .into()],
+        );
+        let without_script = Plugin::with_masters(&[], vec![]);
+
+        let diagnostics = registry.check_load_order(&[("Other.esp", &without_script), ("Scripted.esp", &with_script)]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error[no-scripts]: Scripted.esp: SCPT 'MyScript': scripts are not allowed"
+        );
+    }
+}
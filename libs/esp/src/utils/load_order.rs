@@ -0,0 +1,343 @@
+// rust std imports
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// external imports
+use encoding_rs::WINDOWS_1252;
+
+// internal imports
+use crate::prelude::*;
+
+/// The load order settings of a `Morrowind.ini`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MorrowindIni {
+    /// The plugins of the `[Game Files]` section, ordered by their `GameFileN` keys.
+    pub game_files: Vec<String>,
+    /// The archives of the `[Archives]` section, ordered by their `Archive N` keys.
+    pub archives: Vec<String>,
+}
+
+impl MorrowindIni {
+    pub fn from_path(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let (text, _) = WINDOWS_1252.decode_without_bom_handling(&bytes);
+        Ok(Self::parse(&text))
+    }
+
+    /// Parse the text of a `Morrowind.ini`. Other sections, comments and empty values are ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut game_files = vec![];
+        let mut archives = vec![];
+
+        let mut section = String::new();
+        for line in text.lines().map(str::trim) {
+            if line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                section = name.trim().to_ascii_lowercase();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (files, prefix) = match section.as_str() {
+                "game files" => (&mut game_files, "gamefile"),
+                "archives" => (&mut archives, "archive"),
+                _ => continue,
+            };
+            let index = key
+                .trim()
+                .to_ascii_lowercase()
+                .strip_prefix(prefix)
+                .map(|i| i.trim().parse::<u32>());
+            if let (Some(Ok(index)), false) = (index, value.trim().is_empty()) {
+                files.push((index, value.trim().to_owned()));
+            }
+        }
+
+        // the game reads the first of any repeated keys
+        let sorted = |mut files: Vec<(u32, String)>| {
+            files.sort_by_key(|(index, _)| *index);
+            files.dedup_by_key(|(index, _)| *index);
+            files.into_iter().map(|(_, file)| file).collect()
+        };
+
+        Self {
+            game_files: sorted(game_files),
+            archives: sorted(archives),
+        }
+    }
+
+    /// The archives in order of increasing priority, starting with `Morrowind.bsa`, which the game
+    /// always loads first.
+    pub fn archive_order(&self) -> Vec<String> {
+        let mut archives = vec!["Morrowind.bsa".to_owned()];
+        archives.extend(
+            (self.archives.iter())
+                .filter(|archive| !archive.eq_ignore_ascii_case("Morrowind.bsa"))
+                .cloned(),
+        );
+        archives
+    }
+}
+
+/// A plugin in a [`LoadOrder`].
+#[derive(Clone, Debug)]
+pub struct LoadOrderEntry {
+    /// The file name, as listed in `Morrowind.ini`.
+    pub name: String,
+    pub path: PathBuf,
+    pub header: Header,
+    /// The size of the file in bytes, as recorded in the headers of plugins using it as a master.
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl LoadOrderEntry {
+    /// Whether the game loads this plugin before the others, which is decided by its extension.
+    pub fn is_master(&self) -> bool {
+        Path::new(&self.name)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("esm"))
+    }
+}
+
+/// A problem with a [`LoadOrder`], see [`LoadOrder::problems`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoadOrderProblem {
+    /// A master of `plugin` is not in the load order.
+    MissingMaster { plugin: String, master: String },
+    /// A master of `plugin` is loaded after it.
+    MasterLoadedAfter { plugin: String, master: String },
+    /// A master of `plugin` has changed size since `plugin` was saved, so its references to the
+    /// master's records may be wrong.
+    MasterSizeMismatch {
+        plugin: String,
+        master: String,
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl fmt::Display for LoadOrderProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMaster { plugin, master } => write!(f, "{plugin}: master {master} is not loaded"),
+            Self::MasterLoadedAfter { plugin, master } => write!(f, "{plugin}: master {master} is loaded after it"),
+            Self::MasterSizeMismatch {
+                plugin,
+                master,
+                expected,
+                found,
+            } => write!(f, "{plugin}: master {master} is {found} bytes, expected {expected}"),
+        }
+    }
+}
+
+/// The plugins the game loads, in the order it loads them.
+///
+/// ```ignore
+/// let ini = MorrowindIni::from_path("./Morrowind.ini")?;
+/// let load_order = LoadOrder::from_ini("./Data Files", &ini)?;
+/// for problem in load_order.problems() {
+///     println!("{problem}");
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LoadOrder {
+    pub plugins: Vec<LoadOrderEntry>,
+}
+
+impl LoadOrder {
+    /// The plugins of `ini`, see [`LoadOrder::new`].
+    pub fn from_ini(data_files: impl AsRef<Path>, ini: &MorrowindIni) -> io::Result<Self> {
+        Self::new(data_files, &ini.game_files)
+    }
+
+    /// Read the header of each of `game_files` from `data_files`, and sort them as the game does.
+    ///
+    /// File names are matched ignoring ascii case, and repeated names are ignored.
+    pub fn new(data_files: impl AsRef<Path>, game_files: impl IntoIterator<Item = impl AsRef<str>>) -> io::Result<Self> {
+        let data_files = data_files.as_ref();
+
+        let mut this = Self::default();
+        for name in game_files {
+            let name = name.as_ref();
+            if this.position(name).is_some() {
+                continue;
+            }
+
+            let path = find_file(data_files, name);
+            let with_name = |error: io::Error| io::Error::new(error.kind(), format!("{name}: {error}"));
+            let metadata = fs::metadata(&path).map_err(with_name)?;
+            let header = Header::from_path(&path).map_err(with_name)?;
+
+            this.plugins.push(LoadOrderEntry {
+                name: name.to_owned(),
+                path,
+                header,
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+
+        this.sort();
+        Ok(this)
+    }
+
+    /// Sort the plugins as the game does: masters first, then by modification time.
+    ///
+    /// Plugins with the same modification time keep their relative order.
+    pub fn sort(&mut self) {
+        self.plugins.sort_by_key(|plugin| (!plugin.is_master(), plugin.modified));
+    }
+
+    /// The file names of the plugins, in load order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.iter().map(|plugin| plugin.name.as_str())
+    }
+
+    /// The position of the plugin with the given file name, ignoring ascii case.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.plugins.iter().position(|plugin| plugin.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Check that every master of each plugin is loaded before it, with the size it had when the
+    /// plugin was saved.
+    pub fn problems(&self) -> Vec<LoadOrderProblem> {
+        let mut problems = vec![];
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            for (master, expected) in &plugin.header.masters {
+                let (plugin, master) = (plugin.name.clone(), master.clone());
+                match self.position(&master) {
+                    None => problems.push(LoadOrderProblem::MissingMaster { plugin, master }),
+                    Some(position) if position > index => {
+                        problems.push(LoadOrderProblem::MasterLoadedAfter { plugin, master });
+                    }
+                    Some(position) => {
+                        let found = self.plugins[position].size;
+                        if found != *expected {
+                            problems.push(LoadOrderProblem::MasterSizeMismatch {
+                                plugin,
+                                master,
+                                expected: *expected,
+                                found,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// The path of the file named `name` in `dir`, ignoring ascii case.
+fn find_file(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    if path.exists() {
+        return path;
+    }
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .find(|entry| entry.file_name().to_string_lossy().eq_ignore_ascii_case(name))
+        .map_or(path, |entry| entry.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn parse_ini() {
+        let ini = MorrowindIni::parse(
+            "[General]\r\n\
+             GameFile0=Ignored.esp\r\n\
+             [Game Files]\r\n\
+             GameFile1=Tribunal.esm\r\n\
+             GameFile0=Morrowind.esm\r\n\
+             ;GameFile2=Commented.esp\r\n\
+             GameFile2=\r\n\
+             GameFile3 = My Mod.esp \r\n\
+             GameFile1=Repeated.esm\r\n\
+             [Archives]\r\n\
+             Archive 1=Bloodmoon.bsa\r\n\
+             Archive 0=Tribunal.bsa\r\n",
+        );
+        assert_eq!(ini.game_files, ["Morrowind.esm", "Tribunal.esm", "My Mod.esp"]);
+        assert_eq!(ini.archives, ["Tribunal.bsa", "Bloodmoon.bsa"]);
+        assert_eq!(ini.archive_order(), ["Morrowind.bsa", "Tribunal.bsa", "Bloodmoon.bsa"]);
+    }
+
+    #[test]
+    fn load_order() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+
+        let save = |name: &str, masters: &[(&str, u64)], age: u64| -> io::Result<u64> {
+            let header = Header {
+                masters: masters.iter().map(|(name, size)| ((*name).to_owned(), *size)).collect(),
+                ..default()
+            };
+            let path = dir.path().join(name);
+            Plugin {
+                objects: vec![header.into()],
+                ..default()
+            }
+            .save_path(&path)?;
+            fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(time - Duration::from_secs(age))?;
+            Ok(fs::metadata(&path)?.len())
+        };
+
+        let size = save("Morrowind.esm", &[], 100)?;
+        save("Tribunal.esm", &[("Morrowind.esm", size)], 0)?;
+        let early_size = save("early.esp", &[("Missing.esm", 0)], 20)?;
+        save("Late.esp", &[("Morrowind.esm", size + 1), ("Early.esp", early_size)], 10)?;
+
+        let ini = MorrowindIni {
+            game_files: ["Late.esp", "Early.esp", "Tribunal.esm", "Morrowind.esm", "late.esp"]
+                .map(String::from)
+                .into(),
+            ..default()
+        };
+        let load_order = LoadOrder::from_ini(dir.path(), &ini)?;
+        assert_eq!(
+            load_order.names().collect::<Vec<_>>(),
+            ["Morrowind.esm", "Tribunal.esm", "Early.esp", "Late.esp"]
+        );
+        assert_eq!(
+            load_order.problems(),
+            [
+                LoadOrderProblem::MissingMaster {
+                    plugin: "Early.esp".into(),
+                    master: "Missing.esm".into(),
+                },
+                LoadOrderProblem::MasterSizeMismatch {
+                    plugin: "Late.esp".into(),
+                    master: "Morrowind.esm".into(),
+                    expected: size + 1,
+                    found: size,
+                },
+            ]
+        );
+
+        let ini = MorrowindIni {
+            game_files: vec!["Missing.esp".into()],
+            ..default()
+        };
+        let error = LoadOrder::from_ini(dir.path(), &ini).unwrap_err();
+        assert!(error.to_string().starts_with("Missing.esp: "));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,544 @@
+// rust std imports
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+// internal imports
+use crate::prelude::*;
+use crate::utils::csv::{csv_quote, csv_rows};
+
+/// Identifies a translatable string as `(tag, id, field)`.
+///
+/// The text form is `TAG:id:field`, e.g. `BOOK:bk_words_of_the_wind:text`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TranslationKey {
+    pub tag: String,
+    pub id: String,
+    pub field: String,
+}
+
+/// A player-visible string extracted by [`Plugin::translatable_strings`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TranslatableString {
+    pub key: TranslationKey,
+    pub text: String,
+}
+
+/// A translation as read from a PO or CSV file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Translation {
+    pub key: TranslationKey,
+    /// The original text the translation was made from.
+    pub source: String,
+    /// The translated text. Empty if not yet translated.
+    pub target: String,
+}
+
+/// The result of [`Plugin::apply_translations`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TranslationReport {
+    /// The number of strings that were translated.
+    pub applied: usize,
+    /// Strings whose original text has changed since they were translated. These were not applied.
+    pub stale: Vec<TranslationKey>,
+    /// Strings in the plugin without a translation.
+    pub missing: Vec<TranslationKey>,
+    /// Translations for strings that do not exist in the plugin.
+    pub unused: Vec<TranslationKey>,
+}
+
+impl TranslationKey {
+    pub fn new(tag: impl Into<String>, id: impl Into<String>, field: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            id: id.into(),
+            field: field.into(),
+        }
+    }
+
+    /// Parse a key from its `TAG:id:field` text form.
+    pub fn parse(key: &str) -> Option<Self> {
+        let (tag, rest) = key.split_once(':')?;
+        let (id, field) = rest.rsplit_once(':')?;
+        Some(Self::new(tag, id, field))
+    }
+
+    /// Ids are matched case-insensitively.
+    fn normalized(&self) -> Self {
+        Self::new(
+            self.tag.to_ascii_uppercase(),
+            self.id.to_ascii_lowercase(),
+            self.field.to_ascii_lowercase(),
+        )
+    }
+}
+
+impl fmt::Display for TranslationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.tag, self.id, self.field)
+    }
+}
+
+/// Collects the translatable fields of a `TES3Object` as `(field, &text)` pairs.
+///
+/// Pass `mut` as the second argument to borrow the fields mutably.
+macro_rules! translatable_fields {
+    ($object:expr $(, $m:tt)?) => {{
+        let mut fields: Vec<(Cow<'static, str>, &$($m)? String)> = vec![];
+        match $object {
+            TES3Object::Activator(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Alchemy(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Apparatus(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Armor(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Birthsign(object) => {
+                fields.push(("name".into(), &$($m)? object.name));
+                fields.push(("description".into(), &$($m)? object.description));
+            }
+            TES3Object::Book(object) => {
+                fields.push(("name".into(), &$($m)? object.name));
+                fields.push(("text".into(), &$($m)? object.text));
+            }
+            // interior names are ids, see `Plugin::translatable_strings`
+            TES3Object::Cell(object) if object.is_exterior() => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Class(object) => {
+                fields.push(("name".into(), &$($m)? object.name));
+                fields.push(("description".into(), &$($m)? object.description));
+            }
+            TES3Object::Clothing(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Container(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Creature(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::DialogueInfo(object) => fields.push(("text".into(), &$($m)? object.text)),
+            TES3Object::Door(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Faction(object) => {
+                fields.push(("name".into(), &$($m)? object.name));
+                for (i, rank_name) in (&$($m)? object.rank_names).into_iter().enumerate() {
+                    fields.push((format!("rank_names.{i}").into(), rank_name));
+                }
+            }
+            TES3Object::GameSetting(object) => {
+                if let GameSettingValue::String(value) = &$($m)? object.value {
+                    fields.push(("value".into(), value));
+                }
+            }
+            TES3Object::Ingredient(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Light(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Lockpick(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::MagicEffect(object) => fields.push(("description".into(), &$($m)? object.description)),
+            TES3Object::MiscItem(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Npc(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Probe(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Race(object) => {
+                fields.push(("name".into(), &$($m)? object.name));
+                fields.push(("description".into(), &$($m)? object.description));
+            }
+            TES3Object::Region(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::RepairItem(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Skill(object) => fields.push(("description".into(), &$($m)? object.description)),
+            TES3Object::Spell(object) => fields.push(("name".into(), &$($m)? object.name)),
+            TES3Object::Weapon(object) => fields.push(("name".into(), &$($m)? object.name)),
+            _ => {}
+        }
+        fields
+    }};
+}
+
+/// The id used in the [`TranslationKey`] of an object, which must not change when translated.
+fn translation_id(object: &TES3Object) -> Cow<'_, str> {
+    match object {
+        TES3Object::Cell(cell) if cell.is_exterior() => {
+            let (x, y) = cell.data.grid;
+            format!("{x},{y}").into()
+        }
+        _ => object.editor_id(),
+    }
+}
+
+impl Plugin {
+    /// Every non-empty player-visible string in this plugin, in object order.
+    ///
+    /// Exterior cells are identified by their grid coordinates, e.g. `CELL:-3,-2:name`. Interior
+    /// cell names are not included, as they are also the ids doors, scripts and dialogue use to
+    /// refer to the cell; translate them with [`Plugin::rename_cell`] instead.
+    pub fn translatable_strings(&self) -> Vec<TranslatableString> {
+        let mut strings = vec![];
+
+        for object in &self.objects {
+            let tag = object.tag_str();
+            let id = translation_id(object);
+            for (field, text) in translatable_fields!(object) {
+                if !text.is_empty() {
+                    strings.push(TranslatableString {
+                        key: TranslationKey::new(tag, id.as_ref(), field),
+                        text: text.clone(),
+                    });
+                }
+            }
+        }
+
+        strings
+    }
+
+    /// Replace player-visible strings with their translations.
+    ///
+    /// A translation is only applied if its `source` still matches the text in the plugin.
+    pub fn apply_translations<'a>(&mut self, translations: impl IntoIterator<Item = &'a Translation>) -> TranslationReport {
+        let mut pending: HashMap<TranslationKey, &'a Translation> = translations
+            .into_iter()
+            .map(|translation| (translation.key.normalized(), translation))
+            .collect();
+
+        let mut report = TranslationReport::default();
+
+        for object in &mut self.objects {
+            let tag = object.tag_str().to_owned();
+            let id = translation_id(object).into_owned();
+            for (field, text) in translatable_fields!(object, mut) {
+                if text.is_empty() {
+                    continue;
+                }
+                let key = TranslationKey::new(tag.as_str(), id.as_str(), field);
+                match pending.remove(&key.normalized()) {
+                    Some(translation) if !translation.target.is_empty() => {
+                        if *text == translation.target {
+                            report.applied += 1; // already translated
+                        } else if *text == translation.source {
+                            text.clone_from(&translation.target);
+                            report.applied += 1;
+                        } else {
+                            report.stale.push(key);
+                        }
+                    }
+                    _ => report.missing.push(key),
+                }
+            }
+        }
+
+        report.unused = pending.into_values().map(|translation| translation.key.clone()).collect();
+        report.unused.sort_unstable();
+
+        report
+    }
+}
+
+/// Write strings as a gettext PO template, using each key as the `msgctxt`.
+pub fn save_po(strings: &[TranslatableString], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "msgid \"\"")?;
+    writeln!(writer, "msgstr \"Content-Type: text/plain; charset=UTF-8\\n\"")?;
+
+    for string in strings {
+        writeln!(writer)?;
+        writeln!(writer, "msgctxt {}", po_quote(&string.key.to_string()))?;
+        writeln!(writer, "msgid {}", po_quote(&string.text))?;
+        writeln!(writer, "msgstr \"\"")?;
+    }
+
+    Ok(())
+}
+
+/// Read translations from a gettext PO file. Entries marked as fuzzy are skipped.
+pub fn load_po(reader: impl BufRead) -> io::Result<Vec<Translation>> {
+    #[derive(Default)]
+    struct Entry {
+        fuzzy: bool,
+        context: Option<String>,
+        id: String,
+        target: String,
+    }
+
+    let mut translations = vec![];
+    let mut entry = Entry::default();
+    let mut current: Option<&str> = None;
+
+    let mut finish = |entry: Entry| -> io::Result<()> {
+        let Some(context) = entry.context else {
+            return Ok(()); // header or untagged entry
+        };
+        let Some(key) = TranslationKey::parse(&context) else {
+            return Reader::error(format!("Invalid translation key: {context}"));
+        };
+        if !entry.fuzzy {
+            translations.push(Translation {
+                key,
+                source: entry.id,
+                target: entry.target,
+            });
+        }
+        Ok(())
+    };
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            // comments come before the entry they describe, so they close the previous one
+            if entry.context.is_some() || !entry.id.is_empty() {
+                finish(std::mem::take(&mut entry))?;
+                current = None;
+            }
+            if comment.starts_with(',') && comment.contains("fuzzy") {
+                entry.fuzzy = true;
+            }
+            continue;
+        }
+
+        let (keyword, value) = match line.split_once(' ') {
+            Some((keyword, value)) if !line.starts_with('"') => (Some(keyword), value),
+            _ => (None, line),
+        };
+        let value = po_unquote(value)?;
+
+        match keyword {
+            Some("msgctxt") => {
+                if entry.context.is_some() || !entry.id.is_empty() {
+                    finish(std::mem::take(&mut entry))?;
+                }
+                entry.context = Some(value);
+                current = Some("msgctxt");
+            }
+            Some("msgid") => {
+                if !entry.id.is_empty() {
+                    finish(std::mem::take(&mut entry))?;
+                }
+                entry.id = value;
+                current = Some("msgid");
+            }
+            Some("msgstr") => {
+                entry.target = value;
+                current = Some("msgstr");
+            }
+            Some(keyword) => {
+                return Reader::error(format!("Unsupported PO keyword: {keyword}"));
+            }
+            None => match current {
+                Some("msgctxt") => entry.context.get_or_insert_with(String::new).push_str(&value),
+                Some("msgid") => entry.id.push_str(&value),
+                Some("msgstr") => entry.target.push_str(&value),
+                _ => return Reader::error(format!("Unexpected PO line: {line}")),
+            },
+        }
+    }
+    finish(entry)?;
+
+    Ok(translations)
+}
+
+fn po_quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn po_unquote(text: &str) -> io::Result<String> {
+    let Some(inner) = text.strip_prefix('"').and_then(|text| text.strip_suffix('"')) else {
+        return Reader::error(format!("Expected quoted PO string: {text}"));
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('r') => unquoted.push('\r'),
+            Some('t') => unquoted.push('\t'),
+            Some(c @ ('"' | '\\')) => unquoted.push(c),
+            other => return Reader::error(format!("Invalid PO escape sequence: \\{}", other.unwrap_or_default())),
+        }
+    }
+
+    Ok(unquoted)
+}
+
+/// Write strings as CSV with `key,source,translation` columns.
+pub fn save_csv(strings: &[TranslatableString], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "key,source,translation")?;
+    for string in strings {
+        writeln!(writer, "{},{},", csv_quote(&string.key.to_string()), csv_quote(&string.text))?;
+    }
+    Ok(())
+}
+
+/// Read translations from CSV with `key,source,translation` columns and a header row.
+pub fn load_csv(mut reader: impl BufRead) -> io::Result<Vec<Translation>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut translations = vec![];
+    for (i, row) in csv_rows(&text)?.into_iter().enumerate().skip(1) {
+        if row.iter().all(String::is_empty) {
+            continue;
+        }
+        let [key, source, target] =
+            <[String; 3]>::try_from(row).or_else(|_| Reader::error(format!("Expected 3 columns on CSV row {}", i + 1)))?;
+        let Some(key) = TranslationKey::parse(&key) else {
+            return Reader::error(format!("Invalid translation key: {key}"));
+        };
+        translations.push(Translation { key, source, target });
+    }
+
+    Ok(translations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin() -> Plugin {
+        Plugin {
+            objects: vec![
+                Book {
+                    id: "bk_test".into(),
+                    name: "A \"Test\", Book".into(),
+                    text: "Line one\nLine two".into(),
+                    ..default()
+                }
+                .into(),
+                Faction {
+                    id: "Guild".into(),
+                    name: "Guild".into(),
+                    rank_names: vec!["Novice".into(), "Master".into()],
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        }
+    }
+
+    fn translate(translations: &mut [Translation]) {
+        for translation in translations {
+            translation.target = translation.source.to_uppercase();
+        }
+    }
+
+    #[test]
+    fn po_round_trip() {
+        let plugin = plugin();
+        let strings = plugin.translatable_strings();
+        assert_eq!(strings.len(), 5);
+
+        let mut bytes = vec![];
+        save_po(&strings, &mut bytes).unwrap();
+        let mut translations = load_po(&bytes[..]).unwrap();
+        assert_eq!(translations.len(), 5);
+        assert_eq!(translations[1].key.to_string(), "BOOK:bk_test:text");
+        assert_eq!(translations[1].source, "Line one\nLine two");
+
+        translate(&mut translations);
+        translations[4].source = "Changed".into();
+        translations.push(Translation {
+            key: TranslationKey::new("BOOK", "bk_missing", "name"),
+            ..default()
+        });
+
+        let mut plugin = plugin;
+        let report = plugin.apply_translations(&translations);
+        assert_eq!(report.applied, 4);
+        assert_eq!(report.stale, [TranslationKey::new("FACT", "Guild", "rank_names.1")]);
+        assert_eq!(report.unused, [TranslationKey::new("BOOK", "bk_missing", "name")]);
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn po_fuzzy() {
+        let po = r#"msgid ""
+msgstr "Content-Type: text/plain; charset=UTF-8\n"
+
+msgctxt "BOOK:bk_test:name"
+msgid "Book"
+msgstr "Livre"
+
+#, fuzzy
+msgctxt "BOOK:bk_test:text"
+msgid "Text"
+msgstr "Texte"
+
+# translator comment
+msgctxt "FACT:Guild:name"
+msgid "Guild"
+msgstr "Guilde"
+"#;
+        let translations = load_po(po.as_bytes()).unwrap();
+        let targets: Vec<_> = translations.iter().map(|translation| translation.target.as_str()).collect();
+        assert_eq!(targets, ["Livre", "Guilde"]);
+    }
+
+    #[test]
+    fn translate_twice() {
+        let mut exterior = Cell {
+            name: "Balmora".into(),
+            ..default()
+        };
+        exterior.data.grid = (-3, -2);
+        let mut interior = Cell {
+            name: "Balmora, Guild of Mages".into(),
+            ..default()
+        };
+        interior.data.flags |= CellFlags::IS_INTERIOR;
+
+        let mut plugin = plugin();
+        plugin.objects.extend([exterior.into(), interior.into()]);
+
+        let strings = plugin.translatable_strings();
+        assert_eq!(strings.len(), 6);
+        assert_eq!(strings[5].key.to_string(), "CELL:-3,-2:name");
+
+        let mut bytes = vec![];
+        save_po(&strings, &mut bytes).unwrap();
+        let mut translations = load_po(&bytes[..]).unwrap();
+        translate(&mut translations);
+
+        let report = plugin.apply_translations(&translations);
+        assert_eq!(report.applied, 6);
+        assert!(report.stale.is_empty() && report.missing.is_empty() && report.unused.is_empty());
+
+        // the keys are unchanged by translating
+        let translated = plugin.translatable_strings();
+        let keys = |strings: &[TranslatableString]| strings.iter().map(|string| string.key.clone()).collect::<Vec<_>>();
+        assert_eq!(keys(&translated), keys(&strings));
+        assert_eq!(translated[5].text, "BALMORA");
+
+        // interior cells keep their names
+        let interior = plugin.objects_of_type::<Cell>().find(|cell| cell.is_interior()).unwrap();
+        assert_eq!(interior.name, "Balmora, Guild of Mages");
+
+        let report = plugin.apply_translations(&translations);
+        assert_eq!(report.applied, 6);
+        assert!(report.stale.is_empty() && report.missing.is_empty() && report.unused.is_empty());
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let strings = plugin().translatable_strings();
+
+        let mut bytes = vec![];
+        save_csv(&strings, &mut bytes).unwrap();
+        let translations = load_csv(&bytes[..]).unwrap();
+
+        assert_eq!(translations.len(), strings.len());
+        for (translation, string) in translations.iter().zip(&strings) {
+            assert_eq!(translation.key, string.key);
+            assert_eq!(translation.source, string.text);
+            assert!(translation.target.is_empty());
+        }
+    }
+}
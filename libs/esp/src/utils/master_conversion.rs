@@ -0,0 +1,408 @@
+// rust std imports
+use std::io::{BufRead, Write};
+
+// internal imports
+use crate::prelude::*;
+use crate::utils::csv::{csv_quote, csv_rows};
+use crate::utils::script_text::{rewrite_script_tokens, ScriptName, ScriptToken};
+
+/// Maps cell and topic names from one localized edition of a master file to another.
+///
+/// Names are matched case-insensitively.
+#[derive(Clone, Debug, Default)]
+pub struct MasterConversion {
+    cells: HashMap<String, (String, String)>,
+    topics: HashMap<String, (String, String)>,
+}
+
+impl MasterConversion {
+    pub fn new() -> Self {
+        default()
+    }
+
+    pub fn insert_cell(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        insert_mapping(&mut self.cells, from.into(), to.into());
+    }
+
+    pub fn insert_topic(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        insert_mapping(&mut self.topics, from.into(), to.into());
+    }
+
+    /// The converted name of a cell, if it has one.
+    pub fn cell(&self, name: &str) -> Option<&str> {
+        self.cells.get(&name.to_lowercase()).map(|(_, to)| to.as_str())
+    }
+
+    /// The converted name of a dialogue topic, if it has one.
+    pub fn topic(&self, name: &str) -> Option<&str> {
+        self.topics.get(&name.to_lowercase()).map(|(_, to)| to.as_str())
+    }
+
+    /// All cell mappings as `(from, to)` pairs.
+    pub fn cells(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cells.values().map(|(from, to)| (from.as_str(), to.as_str()))
+    }
+
+    /// All topic mappings as `(from, to)` pairs.
+    pub fn topics(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.topics.values().map(|(from, to)| (from.as_str(), to.as_str()))
+    }
+
+    /// The conversion in the opposite direction.
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self::new();
+        for (from, to) in self.cells() {
+            reversed.insert_cell(to, from);
+        }
+        for (from, to) in self.topics() {
+            reversed.insert_topic(to, from);
+        }
+        reversed
+    }
+
+    /// Derive mappings by comparing two editions of the same master, record by record.
+    ///
+    /// Exterior cells are matched by grid, interior cells by the indices of the references they
+    /// contain, and topics by the ids of their dialogue infos.
+    pub fn from_masters(source: &Plugin, target: &Plugin) -> Self {
+        let mut this = Self::new();
+
+        let target_cells: HashMap<_, _> = target
+            .objects_of_type::<Cell>()
+            .filter_map(|cell| Some((cell_key(cell)?, cell)))
+            .collect();
+        for cell in source.objects_of_type::<Cell>() {
+            if let Some(other) = cell_key(cell).and_then(|key| target_cells.get(&key)) {
+                if !cell.name.is_empty() && !other.name.is_empty() && cell.name != other.name {
+                    this.insert_cell(&cell.name, &other.name);
+                }
+            }
+        }
+
+        let target_topics: HashMap<_, _> = topics_by_info(target)
+            .into_iter()
+            .map(|(topic, info)| (info, topic))
+            .collect();
+        for (topic, info) in topics_by_info(source) {
+            if let Some(other) = target_topics.get(info) {
+                if topic != *other {
+                    this.insert_topic(topic, *other);
+                }
+            }
+        }
+
+        this
+    }
+
+    /// Read mappings from CSV with `kind,from,to` columns, where kind is `cell` or `topic`.
+    pub fn load_csv(mut reader: impl BufRead) -> io::Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let mut this = Self::new();
+        for (i, row) in csv_rows(&text)?.into_iter().enumerate().skip(1) {
+            if row.iter().all(String::is_empty) {
+                continue;
+            }
+            let [kind, from, to] = <[String; 3]>::try_from(row)
+                .or_else(|_| Reader::error(format!("Expected 3 columns on CSV row {}", i + 1)))?;
+            match kind.as_str() {
+                "cell" => this.insert_cell(from, to),
+                "topic" => this.insert_topic(from, to),
+                _ => return Reader::error(format!("Unknown mapping kind on CSV row {}: {kind}", i + 1)),
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Write mappings as CSV with `kind,from,to` columns.
+    pub fn save_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut rows: Vec<_> = (self.cells().map(|(from, to)| ("cell", from, to)))
+            .chain(self.topics().map(|(from, to)| ("topic", from, to)))
+            .collect();
+        rows.sort_unstable();
+
+        writeln!(writer, "kind,from,to")?;
+        for (kind, from, to) in rows {
+            writeln!(writer, "{kind},{},{}", csv_quote(from), csv_quote(to))?;
+        }
+
+        Ok(())
+    }
+
+    fn convert_cell(&self, name: &mut String) -> bool {
+        convert(name, self.cell(name))
+    }
+
+    fn convert_topic(&self, name: &mut String) -> bool {
+        convert(name, self.topic(name))
+    }
+
+    fn convert_script(&self, text: &mut String) -> bool {
+        let rewritten = rewrite_script_tokens(text, |kind, token| {
+            let name = match token {
+                ScriptToken::Quoted(name) | ScriptToken::Word(name) => name,
+            };
+            match kind {
+                ScriptName::Cell => self.cell(name).map(str::to_owned),
+                ScriptName::Topic => self.topic(name).map(str::to_owned),
+                ScriptName::Other => None,
+            }
+        });
+        convert(text, rewritten.as_deref())
+    }
+}
+
+impl Plugin {
+    /// Rewrite every cell and topic name in this plugin using the given conversion.
+    ///
+    /// Covers cells, path grids, dialogue topics, dialogue info speaker cells, `NotCell` filters
+    /// and scripts, door destinations, and actor travel destinations and AI packages. Script text
+    /// is updated, but compiled script bytecode is not, so scripts must be recompiled afterwards.
+    ///
+    /// Returns the number of fields that were changed.
+    pub fn convert_master_edition(&mut self, conversion: &MasterConversion) -> usize {
+        let mut changed = 0;
+        let mut count = |converted: bool| changed += usize::from(converted);
+
+        for object in &mut self.objects {
+            match object {
+                TES3Object::Cell(cell) => {
+                    count(conversion.convert_cell(&mut cell.name));
+                    for reference in cell.references.values_mut() {
+                        if let Some(destination) = &mut reference.destination {
+                            count(conversion.convert_cell(&mut destination.cell));
+                        }
+                    }
+                }
+                TES3Object::PathGrid(path_grid) => {
+                    count(conversion.convert_cell(&mut path_grid.cell));
+                }
+                TES3Object::Dialogue(dialogue) => {
+                    count(conversion.convert_topic(&mut dialogue.id));
+                }
+                TES3Object::DialogueInfo(info) => {
+                    count(conversion.convert_cell(&mut info.speaker_cell));
+                    for filter in &mut info.filters {
+                        if filter.filter_type == FilterType::NotCell {
+                            count(conversion.convert_cell(&mut filter.id));
+                        }
+                    }
+                    count(conversion.convert_script(&mut info.script_text));
+                }
+                TES3Object::Script(script) => {
+                    count(conversion.convert_script(&mut script.text));
+                }
+                TES3Object::Npc(npc) => {
+                    for destination in &mut npc.travel_destinations {
+                        count(conversion.convert_cell(&mut destination.cell));
+                    }
+                    for package in &mut npc.ai_packages {
+                        count(convert_package(conversion, package));
+                    }
+                }
+                TES3Object::Creature(creature) => {
+                    for destination in &mut creature.travel_destinations {
+                        count(conversion.convert_cell(&mut destination.cell));
+                    }
+                    for package in &mut creature.ai_packages {
+                        count(convert_package(conversion, package));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        changed
+    }
+}
+
+fn insert_mapping(map: &mut HashMap<String, (String, String)>, from: String, to: String) {
+    map.insert(from.to_lowercase(), (from, to));
+}
+
+fn convert(value: &mut String, converted: Option<&str>) -> bool {
+    match converted {
+        Some(converted) if converted != value => {
+            converted.clone_into(value);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn convert_package(conversion: &MasterConversion, package: &mut AiPackage) -> bool {
+    match package {
+        AiPackage::Escort(package) => conversion.convert_cell(&mut package.cell),
+        AiPackage::Follow(package) => conversion.convert_cell(&mut package.cell),
+        _ => false,
+    }
+}
+
+/// Identifies the same cell across editions of a master.
+#[derive(Debug, Eq, Hash, PartialEq)]
+enum CellKey {
+    Exterior(i32, i32),
+    Interior(Vec<(u32, u32)>),
+}
+
+fn cell_key(cell: &Cell) -> Option<CellKey> {
+    if let Some((x, y)) = cell.exterior_coords() {
+        return Some(CellKey::Exterior(x, y));
+    }
+    // empty interiors cannot be told apart
+    if cell.references.is_empty() {
+        return None;
+    }
+    let mut indices: Vec<_> = cell.references.keys().copied().collect();
+    indices.sort_unstable();
+    Some(CellKey::Interior(indices))
+}
+
+/// Each topic paired with the id of its first info.
+fn topics_by_info(plugin: &Plugin) -> Vec<(&str, &str)> {
+    let mut topics = vec![];
+    let mut current = None;
+    for object in &plugin.objects {
+        match object {
+            TES3Object::Dialogue(dialogue) => current = Some(dialogue.id.as_str()),
+            TES3Object::DialogueInfo(info) => {
+                if let Some(topic) = current.take() {
+                    topics.push((topic, info.id.as_str()));
+                }
+            }
+            _ => {}
+        }
+    }
+    topics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master(cell_name: &str, topic: &str) -> Plugin {
+        let mut interior = Cell {
+            name: cell_name.into(),
+            ..default()
+        };
+        interior.data.flags |= CellFlags::IS_INTERIOR;
+        interior.references.insert((0, 1), Reference::default());
+        interior.references.insert((0, 2), Reference::default());
+        let mut exterior = Cell {
+            name: "Seyda Neen".into(),
+            ..default()
+        };
+        exterior.data.grid = (-2, -9);
+
+        Plugin {
+            objects: vec![
+                interior.into(),
+                exterior.into(),
+                Dialogue {
+                    id: topic.into(),
+                    ..default()
+                }
+                .into(),
+                DialogueInfo {
+                    id: "19511310302976825065".into(),
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn from_masters() {
+        let english = master("Seyda Neen, Census and Excise Office", "little secret");
+        let german = master("Seyda Neen, Zensus- und Steuerbüro", "kleines Geheimnis");
+
+        let conversion = MasterConversion::from_masters(&english, &german);
+        assert_eq!(conversion.cells().count(), 1);
+        assert_eq!(
+            conversion.cell("seyda neen, census and excise office"),
+            Some("Seyda Neen, Zensus- und Steuerbüro")
+        );
+        assert_eq!(conversion.topic("Little Secret"), Some("kleines Geheimnis"));
+        assert_eq!(conversion.reversed().topic("kleines geheimnis"), Some("little secret"));
+    }
+
+    #[test]
+    fn convert_master_edition() {
+        let mut conversion = MasterConversion::new();
+        conversion.insert_cell("Seyda Neen, Census and Excise Office", "Seyda Neen, Zensus- und Steuerbüro");
+        conversion.insert_topic("little secret", "kleines Geheimnis");
+
+        let mut plugin = Plugin {
+            objects: vec![
+                Npc {
+                    id: "fargoth".into(),
+                    ai_packages: vec![AiPackage::Escort(AiEscortPackage {
+                        cell: "Seyda Neen, Census and Excise Office".into(),
+                        ..default()
+                    })],
+                    ..default()
+                }
+                .into(),
+                Script {
+                    id: "escort".into(),
+                    text: concat!(
+                        "AiEscortCell \"little secret\" \"Seyda Neen, Census and Excise Office\" 0 0 0 0\n",
+                        "AddTopic \"little secret\"\n",
+                        "PositionCell 0 0 0 0 \"little secret\"",
+                    )
+                    .into(),
+                    ..default()
+                }
+                .into(),
+                DialogueInfo {
+                    id: "1".into(),
+                    filters: vec![
+                        Filter {
+                            filter_type: FilterType::NotCell,
+                            id: "Seyda Neen, Census and Excise Office".into(),
+                            ..default()
+                        },
+                        Filter {
+                            filter_type: FilterType::NotId,
+                            id: "Seyda Neen, Census and Excise Office".into(),
+                            ..default()
+                        },
+                    ],
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        };
+
+        assert_eq!(plugin.convert_master_edition(&conversion), 3);
+        let script = plugin.objects_of_type::<Script>().next().unwrap();
+        // only the cell argument of AiEscortCell is a cell, the actor is left alone
+        assert_eq!(
+            script.text,
+            concat!(
+                "AiEscortCell \"little secret\" \"Seyda Neen, Zensus- und Steuerbüro\" 0 0 0 0\n",
+                "AddTopic \"kleines Geheimnis\"\n",
+                "PositionCell 0 0 0 0 \"little secret\"",
+            )
+        );
+        let npc = plugin.objects_of_type::<Npc>().next().unwrap();
+        assert_eq!(
+            npc.ai_packages[0],
+            AiPackage::Escort(AiEscortPackage {
+                cell: "Seyda Neen, Zensus- und Steuerbüro".into(),
+                ..default()
+            })
+        );
+        // only cell filters hold cell names
+        let info = plugin.objects_of_type::<DialogueInfo>().next().unwrap();
+        assert_eq!(info.filters[0].id, "Seyda Neen, Zensus- und Steuerbüro");
+        assert_eq!(info.filters[1].id, "Seyda Neen, Census and Excise Office");
+        assert_eq!(plugin.convert_master_edition(&conversion), 0);
+    }
+}
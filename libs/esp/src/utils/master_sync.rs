@@ -0,0 +1,253 @@
+// rust std imports
+use std::fs;
+use std::mem;
+use std::path::Path;
+
+// internal imports
+use crate::prelude::*;
+
+/// The changes made by [`Plugin::sync_masters`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MasterSync {
+    /// The masters that were removed because nothing used them.
+    pub removed: Vec<String>,
+    /// The masters whose recorded size changed, as `(name, old size, new size)`.
+    pub resized: Vec<(String, u64, u64)>,
+}
+
+impl Plugin {
+    /// Update [`Header::masters`] to list only the masters this plugin uses, with their current
+    /// file sizes.
+    ///
+    /// `masters` holds the path and contents of each master listed in the header, matched by file
+    /// name ignoring ascii case. A master is used if this plugin has references with its
+    /// `mast_index`, overrides records it defines, or refers to an id it defines from any field,
+    /// such as placed objects, inventories, leveled lists, AI packages, dialogue filters or script
+    /// text.
+    ///
+    /// Unused masters are removed, and the `mast_index` of references to later masters is updated
+    /// to match. Nothing is changed if a master is missing from `masters` or its size cannot be
+    /// read.
+    pub fn sync_masters(&mut self, masters: &[(impl AsRef<Path>, &Self)]) -> io::Result<MasterSync> {
+        let Some(header) = self.header() else {
+            return Reader::error("Plugin has no header");
+        };
+        let old_masters = header.masters.clone();
+
+        // the path and contents of each master, in header order
+        let mut files = vec![];
+        for (name, _) in &old_masters {
+            let file = masters.iter().find(|(path, _)| {
+                (path.as_ref().file_name()).is_some_and(|file_name| file_name.to_string_lossy().eq_ignore_ascii_case(name))
+            });
+            let Some((path, master)) = file else {
+                return Reader::error(format!("Master {name} was not provided"));
+            };
+            files.push((path.as_ref(), *master, fs::metadata(path)?.len()));
+        }
+
+        let used = self.used_masters(&files.iter().map(|(_, master, _)| *master).collect::<Vec<_>>());
+
+        // `mast_index` is 1-based, with 0 meaning this plugin
+        let mut sync = MasterSync::default();
+        let mut new_masters = vec![];
+        let mut new_indices = vec![0];
+        let mut next_index = 0;
+        for (((name, old_size), (_, _, new_size)), used) in old_masters.iter().zip(&files).zip(used) {
+            if !used {
+                sync.removed.push(name.clone());
+                new_indices.push(0);
+                continue;
+            }
+            if old_size != new_size {
+                sync.resized.push((name.clone(), *old_size, *new_size));
+            }
+            new_masters.push((name.clone(), *new_size));
+            next_index += 1;
+            new_indices.push(next_index);
+        }
+
+        if !sync.removed.is_empty() {
+            for cell in self.objects_of_type_mut::<Cell>() {
+                cell.references = mem::take(&mut cell.references)
+                    .into_iter()
+                    .map(|((mast_index, refr_index), mut reference)| {
+                        let mast_index = new_indices.get(mast_index as usize).copied().unwrap_or(mast_index);
+                        reference.mast_index = mast_index;
+                        ((mast_index, refr_index), reference)
+                    })
+                    .collect();
+            }
+        }
+
+        if let Some(header) = self.header_mut() {
+            header.masters = new_masters;
+        }
+
+        Ok(sync)
+    }
+
+    /// Whether each of `masters` is used by this plugin, see [`Plugin::sync_masters`].
+    fn used_masters(&self, masters: &[&Self]) -> Vec<bool> {
+        // the records this plugin overrides, and every id it refers to
+        let overrides: HashSet<_> = self.objects.iter().filter_map(TES3Object::override_key).collect();
+        let referenced = self.referenced_ids();
+
+        (1..)
+            .zip(masters)
+            .map(|(mast_index, master)| {
+                let has_references = (self.objects_of_type::<Cell>())
+                    .any(|cell| cell.references.keys().any(|(other, _)| *other == mast_index));
+                has_references
+                    || master
+                        .objects
+                        .iter()
+                        .filter_map(TES3Object::override_key)
+                        .any(|key| overrides.contains(&key) || referenced.contains(&key.1))
+            })
+            .collect()
+    }
+}
+
+impl TES3Object {
+    /// The key by which an override is matched to the record it overrides: the record tag and
+    /// the id in ascii lowercase, with exterior cells identified by grid.
+    pub(crate) fn override_key(&self) -> Option<([u8; 4], String)> {
+        let id = match self {
+            Self::Header(_) => return None,
+            Self::Cell(cell) => match cell.exterior_coords() {
+                Some((x, y)) => format!("{x},{y}"),
+                None => cell.name.to_ascii_lowercase(),
+            },
+            _ => self.editor_id_ascii_lowercase().into_owned(),
+        };
+        (!id.is_empty()).then_some((*self.tag(), id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc(id: &str) -> TES3Object {
+        Npc {
+            id: id.into(),
+            ..default()
+        }
+        .into()
+    }
+
+    fn reference(mast_index: u32, refr_index: u32, id: &str) -> ((u32, u32), Reference) {
+        let reference = Reference {
+            mast_index,
+            refr_index,
+            id: id.into(),
+            ..default()
+        };
+        ((mast_index, refr_index), reference)
+    }
+
+    #[test]
+    fn sync_masters() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let paths = ["Morrowind.esm", "Tribunal.esm", "Bloodmoon.esm", "Unused.esp"].map(|name| dir.path().join(name));
+        for (path, size) in paths.iter().zip([10, 20, 30, 40]) {
+            fs::write(path, vec![0; size])?;
+        }
+
+        let morrowind = Plugin::with_masters(&[], vec![npc("fargoth")]);
+        let tribunal = Plugin::with_masters(&[], vec![npc("barenziah")]);
+        let bloodmoon = Plugin::with_masters(&[], vec![]);
+        let unused = Plugin::with_masters(&[], vec![npc("vivec")]);
+
+        let mut cell = Cell {
+            name: "Seyda Neen, Arrille's Tradehouse".into(),
+            ..default()
+        };
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        cell.references
+            .extend([reference(0, 1, "barenziah"), reference(3, 5, "ex_common_door")]);
+
+        let mut plugin = Plugin::with_masters(
+            &[
+                ("Morrowind.esm", 1),
+                ("Tribunal.esm", 20),
+                ("Bloodmoon.esm", 3),
+                ("Unused.esp", 4),
+            ],
+            vec![npc("Fargoth"), cell.into()],
+        );
+        // masters may be given in any order
+        let masters = [
+            (&paths[3], &unused),
+            (&paths[0], &morrowind),
+            (&paths[1], &tribunal),
+            (&paths[2], &bloodmoon),
+        ];
+        let sync = plugin.sync_masters(&masters)?;
+
+        // morrowind is overridden, tribunal is placed from, and bloodmoon is referenced
+        assert_eq!(sync.removed, ["Unused.esp"]);
+        assert_eq!(
+            sync.resized,
+            [("Morrowind.esm".to_owned(), 1, 10), ("Bloodmoon.esm".to_owned(), 3, 30)]
+        );
+        assert_eq!(plugin.header().unwrap().masters.len(), 3);
+
+        // masters used only from an inventory are kept
+        let mut merchant = Plugin::with_masters(
+            &[("Tribunal.esm", 20)],
+            vec![Npc {
+                id: "arrille".into(),
+                inventory: vec![(1, FixedString("Barenziah".into()))],
+                ..default()
+            }
+            .into()],
+        );
+        let sync = merchant.sync_masters(&masters[2..3])?;
+        assert!(sync.removed.is_empty());
+
+        // morrowind is missing
+        assert!(plugin.sync_masters(&masters[2..]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn remove_unused() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let paths = ["Morrowind.esm", "Tribunal.esm", "Bloodmoon.esm"].map(|name| dir.path().join(name));
+        for path in &paths {
+            fs::write(path, [0; 8])?;
+        }
+
+        let morrowind = Plugin::with_masters(&[], vec![npc("fargoth")]);
+        let tribunal = Plugin::with_masters(&[], vec![npc("barenziah")]);
+        let bloodmoon = Plugin::with_masters(&[], vec![]);
+
+        let mut cell = Cell::default();
+        cell.references
+            .extend([reference(0, 1, "ex_common_door"), reference(3, 5, "ex_common_door")]);
+
+        let mut plugin = Plugin::with_masters(
+            &[("Morrowind.esm", 8), ("Tribunal.esm", 8), ("Bloodmoon.esm", 8)],
+            vec![npc("FARGOTH"), cell.into()],
+        );
+        let sync = plugin.sync_masters(&[(&paths[0], &morrowind), (&paths[1], &tribunal), (&paths[2], &bloodmoon)])?;
+        assert_eq!(sync.removed, ["Tribunal.esm"]);
+        assert!(sync.resized.is_empty());
+
+        let header = plugin.header().unwrap();
+        assert_eq!(
+            header.masters,
+            [("Morrowind.esm".to_owned(), 8), ("Bloodmoon.esm".to_owned(), 8)]
+        );
+
+        let cell = plugin.objects_of_type::<Cell>().next().unwrap();
+        assert_eq!(cell.references[&(2, 5)].mast_index, 2);
+        assert!(cell.references.contains_key(&(0, 1)));
+        assert_eq!(cell.references.len(), 2);
+
+        Ok(())
+    }
+}
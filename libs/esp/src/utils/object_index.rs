@@ -0,0 +1,337 @@
+// rust std imports
+use std::mem;
+
+// internal imports
+use crate::prelude::*;
+
+type IndexKey = ([u8; 4], String);
+
+/// A case-insensitive lookup table from record tag and id to position in [`Plugin::objects`].
+///
+/// Built by [`Plugin::build_index`], and kept up to date by [`Plugin::get_mut`],
+/// [`Plugin::insert_or_replace`] and [`Plugin::remove`]. Ids are compared the same way as
+/// [`EditorId::editor_id_ascii_lowercase`]. When several objects share a tag and id, the last one
+/// is found, as it is the one the game uses.
+///
+/// Other `&mut` methods of [`Plugin`], such as [`Plugin::objects_of_type_mut`], mark the index as
+/// out of date, and it is rebuilt by the next lookup that needs `&mut self`. Lookups never trust
+/// the index for an id it does not contain, but search every object instead, so objects renamed
+/// directly through [`Plugin::objects`] are still found. An object that was renamed directly to
+/// the id of an earlier object may be passed over in favour of the earlier one until
+/// [`Plugin::build_index`] is called again.
+#[derive(Clone, Debug, Default)]
+pub struct ObjectIndex {
+    positions: HashMap<IndexKey, usize>,
+    /// The key of the object at each position, if it has an id.
+    keys: Vec<Option<IndexKey>>,
+    /// Positions handed out by [`Plugin::get_mut`], whose ids may have changed since.
+    pending: Vec<usize>,
+    has_duplicates: bool,
+    /// Whether any object may have been changed without the index knowing which.
+    stale: bool,
+}
+
+/// The outcome of looking up an object in an [`ObjectIndex`].
+enum Lookup {
+    Found(usize),
+    /// Not in the index, though the object may have been given the id since.
+    Missing,
+    Stale,
+}
+
+impl ObjectIndex {
+    fn new(objects: &[TES3Object]) -> Self {
+        let mut this = Self::default();
+        for (position, object) in objects.iter().enumerate() {
+            this.push(index_key(object), position);
+        }
+        this
+    }
+
+    fn push(&mut self, key: Option<IndexKey>, position: usize) {
+        if let Some(key) = &key {
+            self.insert(key.clone(), position);
+        }
+        self.keys.push(key);
+    }
+
+    fn insert(&mut self, key: IndexKey, position: usize) {
+        let existing = self.positions.entry(key).or_insert(position);
+        if *existing != position {
+            self.has_duplicates = true;
+            *existing = position.max(*existing);
+        }
+    }
+
+    fn find(&self, objects: &[TES3Object], tag: [u8; 4], id: &str) -> Lookup {
+        if self.stale || self.keys.len() != objects.len() {
+            return Lookup::Stale;
+        }
+
+        let is_match = |&position: &usize| is_match(&objects[position], tag, id);
+
+        // ids handed out for editing may now match something they did not before
+        let pending = self.pending.iter().copied().filter(is_match).max();
+
+        match self.positions.get(&(tag, id.to_owned())) {
+            Some(position) if is_match(position) => Lookup::Found(pending.map_or(*position, |p| p.max(*position))),
+            Some(_) => Lookup::Stale,
+            None if self.has_duplicates && !self.pending.is_empty() => Lookup::Stale,
+            None => pending.map_or(Lookup::Missing, Lookup::Found),
+        }
+    }
+
+    /// Update the keys of objects that may have been edited, rebuilding if that is not enough.
+    fn refresh(&mut self, objects: &[TES3Object]) {
+        if self.stale || self.keys.len() != objects.len() {
+            *self = Self::new(objects);
+            return;
+        }
+
+        for position in mem::take(&mut self.pending) {
+            let key = index_key(&objects[position]);
+            if key == self.keys[position] {
+                continue;
+            }
+            if let Some(old) = self.keys[position].take() {
+                if self.positions.get(&old) == Some(&position) {
+                    self.positions.remove(&old);
+                    // an earlier object with the old id may now be the one found
+                    if self.has_duplicates {
+                        *self = Self::new(objects);
+                        return;
+                    }
+                }
+            }
+            if let Some(key) = &key {
+                self.insert(key.clone(), position);
+            }
+            self.keys[position] = key;
+        }
+    }
+
+    /// Account for the object at `position` having been removed from `objects`.
+    fn remove(&mut self, objects: &[TES3Object], position: usize) {
+        if self.has_duplicates {
+            *self = Self::new(objects);
+            return;
+        }
+        if let Some(key) = self.keys.remove(position) {
+            self.positions.remove(&key);
+        }
+        for other in self.positions.values_mut() {
+            if *other > position {
+                *other -= 1;
+            }
+        }
+    }
+}
+
+impl Plugin {
+    /// Build an index of objects by tag and id, making [`Plugin::get`] and related methods fast.
+    ///
+    /// The lookup methods work without an index, but search every object each time.
+    pub fn build_index(&mut self) {
+        self.index = Some(ObjectIndex::new(&self.objects));
+    }
+
+    /// Mark the index as out of date, after handing out mutable access to any number of objects.
+    pub(crate) fn invalidate_index(&mut self) {
+        if let Some(index) = &mut self.index {
+            index.stale = true;
+        }
+    }
+
+    /// Find the object of type `T` with the given id, ignoring ascii case.
+    ///
+    /// ```ignore
+    /// let fargoth = plugin.get::<Npc>("Fargoth");
+    /// ```
+    pub fn get<'a, T: 'a + RecordType>(&'a self, id: &str) -> Option<&'a T>
+    where
+        &'a TES3Object: TryInto<&'a T>,
+    {
+        let position = self.position(*T::TAG, &id.to_ascii_lowercase())?;
+        (&self.objects[position]).try_into().ok()
+    }
+
+    /// Find the object of type `T` with the given id, ignoring ascii case.
+    ///
+    /// The index accounts for the id of the returned object being changed.
+    pub fn get_mut<'a, T: 'a + RecordType>(&'a mut self, id: &str) -> Option<&'a mut T>
+    where
+        &'a mut TES3Object: TryInto<&'a mut T>,
+    {
+        let position = self.position_mut(*T::TAG, &id.to_ascii_lowercase())?;
+        if let Some(index) = &mut self.index {
+            index.pending.push(position);
+        }
+        (&mut self.objects[position]).try_into().ok()
+    }
+
+    /// Add `object` to the plugin, replacing any object with the same tag and id (ignoring ascii
+    /// case) in place. Returns the replaced object.
+    ///
+    /// Objects without an id, such as the header, are always added to the end.
+    pub fn insert_or_replace(&mut self, object: impl Into<TES3Object>) -> Option<TES3Object> {
+        let object = object.into();
+        let key = index_key(&object);
+
+        if let Some((tag, id)) = &key {
+            if let Some(position) = self.position_mut(*tag, id) {
+                return Some(mem::replace(&mut self.objects[position], object));
+            }
+        }
+
+        if let Some(index) = &mut self.index {
+            index.refresh(&self.objects);
+            index.push(key, self.objects.len());
+        }
+        self.objects.push(object);
+
+        None
+    }
+
+    /// Remove the object of type `T` with the given id, ignoring ascii case.
+    pub fn remove<T: RecordType>(&mut self, id: &str) -> Option<T>
+    where
+        TES3Object: TryInto<T>,
+    {
+        let position = self.position_mut(*T::TAG, &id.to_ascii_lowercase())?;
+        let object = self.objects.remove(position);
+        if let Some(index) = &mut self.index {
+            index.remove(&self.objects, position);
+        }
+        object.try_into().ok()
+    }
+
+    /// The position of the last object with the given tag and (lowercase) id.
+    fn position(&self, tag: [u8; 4], id: &str) -> Option<usize> {
+        if let Some(index) = &self.index {
+            // objects may have been renamed directly, so missing ids are searched for too
+            if let Lookup::Found(position) = index.find(&self.objects, tag, id) {
+                return Some(position);
+            }
+        }
+        self.objects.iter().rposition(|object| is_match(object, tag, id))
+    }
+
+    /// As [`Plugin::position`], first bringing the index up to date.
+    fn position_mut(&mut self, tag: [u8; 4], id: &str) -> Option<usize> {
+        if let Some(index) = &mut self.index {
+            index.refresh(&self.objects);
+            if matches!(index.find(&self.objects, tag, id), Lookup::Stale) {
+                *index = ObjectIndex::new(&self.objects);
+            }
+        }
+        self.position(tag, id)
+    }
+}
+
+fn index_key(object: &TES3Object) -> Option<IndexKey> {
+    let id = object.editor_id_ascii_lowercase();
+    (!id.is_empty()).then(|| (*object.tag(), id.into_owned()))
+}
+
+fn is_match(object: &TES3Object, tag: [u8; 4], id: &str) -> bool {
+    *object.tag() == tag && object.editor_id_ascii_lowercase() == id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc(id: &str, name: &str) -> TES3Object {
+        Npc {
+            id: id.into(),
+            name: name.into(),
+            ..default()
+        }
+        .into()
+    }
+
+    fn plugin() -> Plugin {
+        Plugin {
+            objects: vec![
+                Header::default().into(),
+                npc("fargoth", "Fargoth"),
+                Static {
+                    id: "Fargoth".into(),
+                    ..default()
+                }
+                .into(),
+                npc("hrisskar flat-foot", "Hrisskar"),
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        for indexed in [false, true] {
+            let mut plugin = plugin();
+            if indexed {
+                plugin.build_index();
+            }
+
+            assert_eq!(plugin.get::<Npc>("Fargoth").unwrap().name, "Fargoth");
+            assert_eq!(plugin.get::<Static>("FARGOTH").unwrap().id, "Fargoth");
+            assert!(plugin.get::<Npc>("vivec").is_none());
+
+            // renaming through `get_mut` is seen by later lookups
+            plugin.get_mut::<Npc>("fargoth").unwrap().id = "Fargoth_2".into();
+            assert!(plugin.get::<Npc>("fargoth").is_none());
+            assert_eq!(plugin.get::<Npc>("fargoth_2").unwrap().name, "Fargoth");
+
+            // replacing keeps the position, inserting appends
+            let old = plugin.insert_or_replace(npc("FARGOTH_2", "Fargoth the Second"));
+            assert!(matches!(old, Some(TES3Object::Npc(npc)) if npc.name == "Fargoth"));
+            assert!(plugin.insert_or_replace(npc("vivec", "Vivec")).is_none());
+            assert_eq!(plugin.objects.len(), 5);
+            assert_eq!(plugin.get::<Npc>("fargoth_2").unwrap().name, "Fargoth the Second");
+            assert_eq!(plugin.get::<Npc>("Vivec").unwrap().name, "Vivec");
+
+            // removing shifts the positions of later objects
+            assert_eq!(plugin.remove::<Static>("fargoth").unwrap().id, "Fargoth");
+            assert!(plugin.get::<Static>("fargoth").is_none());
+            assert_eq!(plugin.get::<Npc>("HRISSKAR FLAT-FOOT").unwrap().name, "Hrisskar");
+            assert_eq!(plugin.get::<Npc>("vivec").unwrap().name, "Vivec");
+        }
+    }
+
+    #[test]
+    fn direct_edits() {
+        let mut plugin = plugin();
+        plugin.build_index();
+
+        // renaming through `objects` is found by searching
+        let TES3Object::Npc(npc) = &mut plugin.objects[1] else {
+            unreachable!("the second object is an npc");
+        };
+        npc.id = "fargoth_2".into();
+        assert_eq!(plugin.get::<Npc>("Fargoth_2").unwrap().name, "Fargoth");
+        assert!(plugin.get::<Npc>("fargoth").is_none());
+
+        // renaming through other `&mut` methods marks the index as out of date
+        let hrisskar = plugin.objects_of_type_mut::<Npc>().last().unwrap();
+        hrisskar.id = "fargoth_2".into();
+        assert_eq!(plugin.get::<Npc>("fargoth_2").unwrap().name, "Hrisskar");
+        assert_eq!(plugin.get_mut::<Npc>("fargoth_2").unwrap().name, "Hrisskar");
+        assert!(plugin.get::<Npc>("hrisskar flat-foot").is_none());
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut plugin = plugin();
+        plugin.objects.push(npc("Fargoth", "Fargoth (override)"));
+        plugin.build_index();
+
+        assert_eq!(plugin.get::<Npc>("fargoth").unwrap().name, "Fargoth (override)");
+        plugin.remove::<Npc>("fargoth");
+        assert_eq!(plugin.get::<Npc>("fargoth").unwrap().name, "Fargoth");
+
+        // the index notices objects being reordered directly
+        plugin.objects.swap(1, 3);
+        assert_eq!(plugin.get::<Npc>("fargoth").unwrap().name, "Fargoth");
+    }
+}
@@ -0,0 +1,404 @@
+// external imports
+use glam::{Affine3A, Vec2, Vec3};
+
+// internal imports
+use crate::prelude::*;
+
+/// A triangle in world space.
+pub type Triangle = [Vec3; 3];
+
+/// Generates a `PathGrid` for a cell from the geometry of its references.
+///
+/// Points are placed on walkable surfaces at `granularity` spacing and connected to their
+/// neighbours wherever an actor could move between them without obstruction.
+#[derive(Clone, Debug, SmartDefault)]
+pub struct PathGridGenerator {
+    /// The spacing between points. Also stored in `PathGridData::granularity`.
+    #[default(256)]
+    pub granularity: u16,
+    /// The steepest walkable surface, in degrees.
+    #[default(45.0)]
+    pub max_slope: f32,
+    /// The largest height difference that may be stepped over between connected points.
+    #[default(48.0)]
+    pub max_step: f32,
+    /// The free space required above a point for an actor to stand there.
+    #[default(128.0)]
+    pub clearance: f32,
+}
+
+impl PathGridGenerator {
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Generate a path grid for `cell`.
+    ///
+    /// The `mesh_triangles` callback receives the id of each referenced object and should return
+    /// the triangles of its mesh in local space, or `None` if the object should not be considered
+    /// (e.g. actors, or objects without collision). Results are cached per id. For exterior cells
+    /// the terrain from `landscape` is included as well.
+    ///
+    /// Meshes should be given as the game collides with them, so that points are placed on the
+    /// same surfaces actors walk on. `NiStream::collision_triangles` does this, using only the
+    /// geometry beneath a `RootCollisionNode` when there is one.
+    pub fn generate(
+        &self,
+        cell: &Cell,
+        landscape: Option<&Landscape>,
+        mut mesh_triangles: impl FnMut(&str) -> Option<Vec<Triangle>>,
+    ) -> io::Result<PathGrid> {
+        if self.granularity == 0 {
+            return Reader::error("Path grid granularity must be greater than zero");
+        }
+
+        let mut meshes: HashMap<String, Option<Vec<Triangle>>> = HashMap::new();
+        let mut triangles = vec![];
+
+        for reference in cell.references.values() {
+            if reference.deleted() {
+                continue;
+            }
+            let id = reference.id.to_ascii_lowercase();
+            let mesh = meshes.entry(id).or_insert_with(|| mesh_triangles(&reference.id));
+            if let Some(mesh) = mesh {
+                let transform = reference.world_transform();
+                triangles.extend(mesh.iter().map(|triangle| transform_triangle(&transform, triangle)));
+            }
+        }
+
+        if let Some(landscape) = landscape.filter(|_| cell.is_exterior()) {
+            let vertices = landscape.calculate_world_vertices();
+            for triangle in landscape.calcuate_triangles() {
+                triangles.push(triangle.map(|i| vertices[i as usize]));
+            }
+        }
+
+        let mut path_grid = PathGrid {
+            cell: cell.name.clone(),
+            data: PathGridData {
+                grid: cell.data.grid,
+                granularity: self.granularity,
+                point_count: 0,
+            },
+            ..default()
+        };
+
+        // Exterior path grid points are relative to the cell origin.
+        #[allow(clippy::cast_precision_loss)]
+        let origin = match cell.exterior_coords() {
+            Some((x, y)) => Vec3::new(x as f32 * 8192.0, y as f32 * 8192.0, 0.0),
+            None => Vec3::ZERO,
+        };
+
+        let index = TriangleIndex::new(triangles, f32::from(self.granularity));
+        let points = self.place_points(&index);
+
+        let mut grid_positions = HashMap::new();
+        for (position, point) in &points {
+            #[allow(clippy::cast_possible_truncation)]
+            let location = (*point - origin).round().to_array().map(|v| v as i32);
+            let i = path_grid.add_point(location)?;
+            path_grid.points[i as usize].auto_generated = 1;
+            grid_positions.entry(*position).or_insert_with(Vec::new).push(i);
+        }
+
+        let mut lists = vec![vec![]; points.len()];
+        for (i, ((x, y), point)) in (0u32..).zip(&points) {
+            for (dx, dy) in [(1, 0), (0, 1), (1, 1), (1, -1)] {
+                let Some(others) = grid_positions.get(&(x + dx, y + dy)) else {
+                    continue;
+                };
+                for &j in others {
+                    if self.can_connect(&index, *point, points[j as usize].1) {
+                        lists[i as usize].push(j);
+                        lists[j as usize].push(i);
+                    }
+                }
+            }
+        }
+        path_grid.set_adjacency_lists(&lists)?;
+
+        Ok(path_grid)
+    }
+
+    /// Find every standable surface at each sample position of the grid.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn place_points(&self, index: &TriangleIndex) -> Vec<((i32, i32), Vec3)> {
+        let Some((min, max)) = index.bounds else {
+            return vec![];
+        };
+
+        let spacing = f32::from(self.granularity);
+        let min_slope = self.max_slope.to_radians().cos();
+
+        let (x0, y0) = ((min.x / spacing).ceil() as i32, (min.y / spacing).ceil() as i32);
+        let (x1, y1) = ((max.x / spacing).floor() as i32, (max.y / spacing).floor() as i32);
+
+        let mut points = vec![];
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let sample = Vec2::new(x as f32 * spacing, y as f32 * spacing);
+
+                let mut surfaces: Vec<f32> = index
+                    .near(sample)
+                    .filter(|triangle| normal(triangle).z >= min_slope)
+                    .filter_map(|triangle| height_at(triangle, sample))
+                    .collect();
+                surfaces.sort_by(f32::total_cmp);
+                surfaces.dedup_by(|a, b| (*a - *b).abs() < 1.0);
+
+                for height in surfaces {
+                    let feet = sample.extend(height + self.max_step);
+                    let head = sample.extend(height + self.clearance);
+                    if !index.obstructed(feet, head) {
+                        points.push(((x, y), sample.extend(height)));
+                    }
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Whether an actor can move directly between two points.
+    fn can_connect(&self, index: &TriangleIndex, a: Vec3, b: Vec3) -> bool {
+        if (a.z - b.z).abs()
+            > self
+                .max_step
+                .max(f32::from(self.granularity) * self.max_slope.to_radians().tan())
+        {
+            return false;
+        }
+        // Check for obstructions at knee and head height.
+        [self.max_step, self.clearance].into_iter().all(|offset| {
+            let offset = Vec3::Z * offset;
+            !index.obstructed(a + offset, b + offset)
+        })
+    }
+}
+
+/// Triangles bucketed on the XY plane for faster spatial queries.
+struct TriangleIndex {
+    triangles: Vec<Triangle>,
+    buckets: HashMap<(i32, i32), Vec<usize>>,
+    bucket_size: f32,
+    bounds: Option<(Vec3, Vec3)>,
+}
+
+impl TriangleIndex {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(triangles: Vec<Triangle>, bucket_size: f32) -> Self {
+        let mut buckets: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        let mut bounds: Option<(Vec3, Vec3)> = None;
+
+        for (i, triangle) in triangles.iter().enumerate() {
+            let min = triangle[0].min(triangle[1]).min(triangle[2]);
+            let max = triangle[0].max(triangle[1]).max(triangle[2]);
+            bounds = Some(bounds.map_or((min, max), |(lo, hi)| (lo.min(min), hi.max(max))));
+
+            let (x0, y0) = ((min.x / bucket_size).floor() as i32, (min.y / bucket_size).floor() as i32);
+            let (x1, y1) = ((max.x / bucket_size).floor() as i32, (max.y / bucket_size).floor() as i32);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    buckets.entry((x, y)).or_default().push(i);
+                }
+            }
+        }
+
+        Self {
+            triangles,
+            buckets,
+            bucket_size,
+            bounds,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn bucket(&self, point: Vec2) -> (i32, i32) {
+        let point = (point / self.bucket_size).floor();
+        (point.x as i32, point.y as i32)
+    }
+
+    /// Triangles that may overlap `point` on the XY plane.
+    fn near(&self, point: Vec2) -> impl Iterator<Item = &Triangle> {
+        let indices = self.buckets.get(&self.bucket(point)).map_or(&[][..], Vec::as_slice);
+        indices.iter().map(|&i| &self.triangles[i])
+    }
+
+    /// Whether the segment from `a` to `b` intersects any triangle.
+    fn obstructed(&self, a: Vec3, b: Vec3) -> bool {
+        let (x0, y0) = self.bucket(a.truncate().min(b.truncate()));
+        let (x1, y1) = self.bucket(a.truncate().max(b.truncate()));
+
+        let mut seen = HashSet::new();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                for &i in self.buckets.get(&(x, y)).into_iter().flatten() {
+                    if seen.insert(i) && segment_intersects(&self.triangles[i], a, b) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+fn transform_triangle(transform: &Affine3A, triangle: &Triangle) -> Triangle {
+    triangle.map(|v| transform.transform_point3(v))
+}
+
+fn normal([v0, v1, v2]: &Triangle) -> Vec3 {
+    (*v1 - *v0).cross(*v2 - *v0).normalize_or_zero()
+}
+
+/// The height of the triangle at `point` on the XY plane, if it covers that point.
+fn height_at([v0, v1, v2]: &Triangle, point: Vec2) -> Option<f32> {
+    let (p0, p1, p2) = (v0.truncate(), v1.truncate(), v2.truncate());
+    let area = (p1 - p0).perp_dot(p2 - p0);
+    if area.abs() < f32::EPSILON {
+        return None;
+    }
+    let weights = Vec3::new(
+        (p2 - p1).perp_dot(point - p1) / area,
+        (p0 - p2).perp_dot(point - p2) / area,
+        (p1 - p0).perp_dot(point - p0) / area,
+    );
+    if weights.min_element() < 0.0 {
+        return None;
+    }
+    Some(weights.dot(Vec3::new(v0.z, v1.z, v2.z)))
+}
+
+/// Möller–Trumbore intersection test between a triangle and the segment from `start` to `end`.
+fn segment_intersects([v0, v1, v2]: &Triangle, start: Vec3, end: Vec3) -> bool {
+    let direction = end - start;
+    let edge1 = *v1 - *v0;
+    let edge2 = *v2 - *v0;
+
+    let pvec = direction.cross(edge2);
+    let det = edge1.dot(pvec);
+    if det.abs() < f32::EPSILON {
+        return false;
+    }
+
+    let inv_det = det.recip();
+    let tvec = start - *v0;
+    let u = tvec.dot(pvec) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return false;
+    }
+
+    let qvec = tvec.cross(edge1);
+    let v = direction.dot(qvec) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+
+    let t = edge2.dot(qvec) * inv_det;
+    (0.0..=1.0).contains(&t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two triangles covering a square on the XY plane.
+    fn floor(size: f32) -> Vec<Triangle> {
+        let [a, b, c, d] = [(0.0, 0.0), (size, 0.0), (0.0, size), (size, size)].map(|(x, y)| Vec3::new(x, y, 0.0));
+        vec![[a, b, d], [a, d, c]]
+    }
+
+    /// Two triangles covering a vertical square on the YZ plane.
+    fn wall(size: f32) -> Vec<Triangle> {
+        let [a, b, c, d] = [(0.0, 0.0), (size, 0.0), (0.0, size), (size, size)].map(|(y, z)| Vec3::new(0.0, y, z));
+        vec![[a, b, d], [a, d, c]]
+    }
+
+    fn interior(references: &[(&str, [f32; 3])]) -> Cell {
+        let mut cell = Cell {
+            name: "Test Cell".into(),
+            ..default()
+        };
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        for (refr_index, (id, translation)) in (1..).zip(references) {
+            let reference = Reference {
+                refr_index,
+                id: (*id).into(),
+                translation: *translation,
+                ..default()
+            };
+            cell.references.insert((0, refr_index), reference);
+        }
+        cell
+    }
+
+    fn meshes(id: &str) -> Option<Vec<Triangle>> {
+        match id {
+            "floor" => Some(floor(1024.0)),
+            "wall" => Some(wall(1024.0)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn flat_floor() -> io::Result<()> {
+        let generator = PathGridGenerator::new();
+        let cell = interior(&[("floor", [0.0, 0.0, 100.0]), ("actor", [512.0, 512.0, 100.0])]);
+        let path_grid = generator.generate(&cell, None, meshes)?;
+
+        // a 5x5 grid of points, each connected to its 8 neighbours
+        assert_eq!(path_grid.cell, "Test Cell");
+        assert_eq!(path_grid.data.granularity, 256);
+        assert_eq!(path_grid.points.len(), 25);
+        assert!(path_grid.points.iter().all(|point| point.location[2] == 100));
+        assert!(path_grid.points.iter().all(|point| point.auto_generated == 1));
+        assert_eq!(path_grid.connections.len(), 2 * (20 + 20 + 32));
+        assert!(path_grid.validate().is_empty());
+
+        let corner = |x, y| {
+            let i = path_grid
+                .points
+                .iter()
+                .position(|point| point.location == [x, y, 100])
+                .unwrap();
+            u32::try_from(i).unwrap()
+        };
+        assert_eq!(path_grid.connections_of(corner(0, 0)).len(), 3);
+        assert_eq!(path_grid.connections_of(corner(512, 512)).len(), 8);
+        assert_eq!(
+            path_grid
+                .shortest_path(corner(0, 0), corner(1024, 1024))
+                .map(|path| path.len()),
+            Some(5)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn obstructed() -> io::Result<()> {
+        let generator = PathGridGenerator::new();
+
+        // a wall between the 3rd and 4th columns cuts 5 straight and 8 diagonal connections
+        let cell = interior(&[("floor", [0.0, 0.0, 100.0]), ("wall", [640.0, 0.0, 100.0])]);
+        let path_grid = generator.generate(&cell, None, meshes)?;
+        assert_eq!(path_grid.points.len(), 25);
+        assert_eq!(path_grid.connections.len(), 2 * (72 - 5 - 8));
+
+        // a ceiling lower than the clearance leaves no room to stand
+        let cell = interior(&[("floor", [0.0, 0.0, 100.0]), ("floor", [0.0, 0.0, 150.0])]);
+        let path_grid = generator.generate(&cell, None, |id| (id == "floor").then(|| floor(1024.0)))?;
+        assert!(path_grid.points.iter().all(|point| point.location[2] == 150));
+
+        let generator = PathGridGenerator {
+            granularity: 0,
+            ..default()
+        };
+        assert!(generator.generate(&cell, None, meshes).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+// rust std imports
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+// internal imports
+use crate::prelude::*;
+
+/// A problem found by [`PathGrid::validate`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathGridIssue {
+    /// `PathGridData::point_count` does not match the number of points.
+    PointCountMismatch { expected: usize, actual: usize },
+    /// The sum of every `PathGridPoint::connection_count` does not match the number of connections.
+    ConnectionCountMismatch { expected: usize, actual: usize },
+    /// A connection refers to a point that does not exist.
+    IndexOutOfRange { from: u32, to: u32 },
+    /// A point is connected to itself.
+    SelfConnection { point: u32 },
+    /// The same connection is listed more than once.
+    DuplicateConnection { from: u32, to: u32 },
+    /// A connection has no matching connection in the opposite direction.
+    OneWayConnection { from: u32, to: u32 },
+}
+
+impl PathGrid {
+    /// The ranges of `connections` belonging to each point, as implied by their `connection_count`.
+    ///
+    /// Ranges are clamped to the length of `connections`.
+    fn connection_ranges(&self) -> Vec<Range<usize>> {
+        let len = self.connections.len();
+        let mut start = 0;
+        self.points
+            .iter()
+            .map(|point| {
+                let end = (start + point.connection_count as usize).min(len);
+                let range = start.min(len)..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
+
+    /// The points connected from `point`.
+    pub fn connections_of(&self, point: u32) -> &[u32] {
+        match self.connection_ranges().get(point as usize) {
+            Some(range) => &self.connections[range.clone()],
+            None => &[],
+        }
+    }
+
+    /// A graph view of this path grid, with one list of outgoing connections per point.
+    pub fn adjacency_lists(&self) -> Vec<Vec<u32>> {
+        self.connection_ranges()
+            .into_iter()
+            .map(|range| self.connections[range].to_vec())
+            .collect()
+    }
+
+    /// Replace all connections with the given adjacency lists, one per point.
+    ///
+    /// Updates `connection_count` and `connections` to match.
+    pub fn set_adjacency_lists(&mut self, lists: &[Vec<u32>]) -> io::Result<()> {
+        if lists.len() != self.points.len() {
+            return Reader::error(format!(
+                "Adjacency list count ({}) does not match point count ({})",
+                lists.len(),
+                self.points.len()
+            ));
+        }
+
+        let mut counts = Vec::with_capacity(lists.len());
+        for list in lists {
+            let Ok(count) = u8::try_from(list.len()) else {
+                return Reader::error(format!("Too many connections for a single point: {}", list.len()));
+            };
+            counts.push(count);
+        }
+
+        for (point, count) in self.points.iter_mut().zip(counts) {
+            point.connection_count = count;
+        }
+        self.connections = lists.concat();
+
+        Ok(())
+    }
+
+    /// Add a new point without any connections, returning its index.
+    pub fn add_point(&mut self, location: [i32; 3]) -> io::Result<u32> {
+        let index = self.points.len();
+        let Ok(point_count) = u16::try_from(index + 1) else {
+            return Reader::error("Too many path grid points");
+        };
+
+        self.points.push(PathGridPoint { location, ..default() });
+        self.data.point_count = point_count;
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(index as u32)
+    }
+
+    /// Remove a point along with every connection to or from it.
+    ///
+    /// Points after the removed point are shifted down by one index.
+    pub fn remove_point(&mut self, point: u32) -> io::Result<PathGridPoint> {
+        if point as usize >= self.points.len() {
+            return Reader::error(format!("Path grid point index out of range: {point}"));
+        }
+
+        let mut lists = self.adjacency_lists();
+        lists.remove(point as usize);
+        for list in &mut lists {
+            list.retain(|&target| target != point);
+            for target in list.iter_mut() {
+                if *target > point {
+                    *target -= 1;
+                }
+            }
+        }
+
+        let removed = self.points.remove(point as usize);
+        self.set_adjacency_lists(&lists)?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            self.data.point_count = self.points.len() as u16;
+        }
+
+        Ok(removed)
+    }
+
+    /// Add a one-way connection. Returns `false` if the connection already existed.
+    pub fn add_connection(&mut self, from: u32, to: u32) -> io::Result<bool> {
+        self.check_indices(from, to)?;
+
+        let mut lists = self.adjacency_lists();
+        if lists[from as usize].contains(&to) {
+            return Ok(false);
+        }
+        lists[from as usize].push(to);
+        self.set_adjacency_lists(&lists)?;
+
+        Ok(true)
+    }
+
+    /// Remove a one-way connection. Returns `false` if the connection did not exist.
+    pub fn remove_connection(&mut self, from: u32, to: u32) -> io::Result<bool> {
+        self.check_indices(from, to)?;
+
+        let mut lists = self.adjacency_lists();
+        let list = &mut lists[from as usize];
+        let len = list.len();
+        list.retain(|&target| target != to);
+        if list.len() == len {
+            return Ok(false);
+        }
+        self.set_adjacency_lists(&lists)?;
+
+        Ok(true)
+    }
+
+    /// Connect two points in both directions, as the Construction Set does.
+    pub fn connect(&mut self, a: u32, b: u32) -> io::Result<()> {
+        self.add_connection(a, b)?;
+        self.add_connection(b, a)?;
+        Ok(())
+    }
+
+    /// Disconnect two points in both directions.
+    pub fn disconnect(&mut self, a: u32, b: u32) -> io::Result<()> {
+        self.remove_connection(a, b)?;
+        self.remove_connection(b, a)?;
+        Ok(())
+    }
+
+    fn check_indices(&self, from: u32, to: u32) -> io::Result<()> {
+        let len = self.points.len();
+        if from as usize >= len || to as usize >= len {
+            return Reader::error(format!("Path grid connection out of range: {from} -> {to}"));
+        }
+        if from == to {
+            return Reader::error(format!("Path grid point cannot connect to itself: {from}"));
+        }
+        Ok(())
+    }
+
+    /// Find the shortest route between two points using A*.
+    ///
+    /// Returns the indices of every point along the route, including `from` and `to`.
+    pub fn shortest_path(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        let len = self.points.len();
+        if from as usize >= len || to as usize >= len {
+            return None;
+        }
+
+        let lists = self.adjacency_lists();
+        let distance = |a: u32, b: u32| self.distance(a, b);
+
+        let mut costs = vec![f32::INFINITY; len];
+        let mut previous = vec![None; len];
+        let mut open = BinaryHeap::new();
+
+        costs[from as usize] = 0.0;
+        open.push(Candidate {
+            estimate: distance(from, to),
+            point: from,
+        });
+
+        while let Some(Candidate { point, estimate }) = open.pop() {
+            if point == to {
+                let mut path = vec![to];
+                while let Some(prev) = previous[*path.last()? as usize] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let cost = costs[point as usize];
+            if estimate > cost + distance(point, to) {
+                continue; // stale entry
+            }
+
+            for &next in &lists[point as usize] {
+                if next as usize >= len {
+                    continue;
+                }
+                let next_cost = cost + distance(point, next);
+                if next_cost < costs[next as usize] {
+                    costs[next as usize] = next_cost;
+                    previous[next as usize] = Some(point);
+                    open.push(Candidate {
+                        estimate: next_cost + distance(next, to),
+                        point: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn distance(&self, a: u32, b: u32) -> f32 {
+        let [ax, ay, az] = self.points[a as usize].location.map(|v| v as f32);
+        let [bx, by, bz] = self.points[b as usize].location.map(|v| v as f32);
+        (ax - bx).hypot(ay - by).hypot(az - bz)
+    }
+
+    /// Check that points and connections are consistent.
+    pub fn validate(&self) -> Vec<PathGridIssue> {
+        let mut issues = vec![];
+
+        let num_points = self.points.len();
+        if self.data.point_count as usize != num_points {
+            issues.push(PathGridIssue::PointCountMismatch {
+                expected: self.data.point_count as usize,
+                actual: num_points,
+            });
+        }
+
+        let num_connections = self.points.iter().map(|p| p.connection_count as usize).sum();
+        if num_connections != self.connections.len() {
+            issues.push(PathGridIssue::ConnectionCountMismatch {
+                expected: num_connections,
+                actual: self.connections.len(),
+            });
+        }
+
+        let lists = self.adjacency_lists();
+        for (from, list) in (0..).zip(&lists) {
+            for (i, &to) in list.iter().enumerate() {
+                if to as usize >= num_points {
+                    issues.push(PathGridIssue::IndexOutOfRange { from, to });
+                } else if to == from {
+                    issues.push(PathGridIssue::SelfConnection { point: from });
+                } else if list[..i].contains(&to) {
+                    issues.push(PathGridIssue::DuplicateConnection { from, to });
+                } else if !lists[to as usize].contains(&from) {
+                    issues.push(PathGridIssue::OneWayConnection { from, to });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+/// A* open set entry, ordered so that [`BinaryHeap`] pops the lowest estimate first.
+struct Candidate {
+    estimate: f32,
+    point: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.point.cmp(&self.point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_grid() -> PathGrid {
+        let mut grid = PathGrid::default();
+        for x in 0..4 {
+            grid.add_point([x * 100, 0, 0]).unwrap();
+        }
+        for i in 0..3 {
+            grid.connect(i, i + 1).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn edit_consistency() {
+        let mut grid = line_grid();
+        assert_eq!(grid.data.point_count, 4);
+        assert_eq!(grid.connections, [1, 0, 2, 1, 3, 2]);
+        assert!(grid.validate().is_empty());
+
+        grid.remove_point(1).unwrap();
+        assert_eq!(grid.data.point_count, 3);
+        assert_eq!(grid.adjacency_lists(), [vec![], vec![2], vec![1]]);
+        assert!(grid.validate().is_empty());
+    }
+
+    #[test]
+    fn shortest_path() {
+        let mut grid = line_grid();
+        assert_eq!(grid.shortest_path(0, 3), Some(vec![0, 1, 2, 3]));
+
+        // a shortcut from the first to the last point
+        grid.connect(0, 3).unwrap();
+        assert_eq!(grid.shortest_path(0, 3), Some(vec![0, 3]));
+
+        grid.disconnect(0, 3).unwrap();
+        grid.disconnect(1, 2).unwrap();
+        assert_eq!(grid.shortest_path(0, 3), None);
+    }
+
+    #[test]
+    fn validate_issues() {
+        let mut grid = line_grid();
+        grid.remove_connection(2, 1).unwrap();
+        grid.connections[0] = 9;
+        assert_eq!(
+            grid.validate(),
+            [
+                PathGridIssue::IndexOutOfRange { from: 0, to: 9 },
+                PathGridIssue::OneWayConnection { from: 1, to: 0 },
+                PathGridIssue::OneWayConnection { from: 1, to: 2 },
+            ]
+        );
+    }
+}
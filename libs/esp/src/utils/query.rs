@@ -0,0 +1,495 @@
+// rust std imports
+use std::borrow::Borrow;
+use std::cell::OnceCell;
+use std::io::Write;
+use std::str::FromStr;
+
+// external imports
+use serde_json::{Map, Value};
+
+// internal imports
+use crate::prelude::*;
+use crate::utils::csv::csv_quote;
+
+/// A filter over records, written in a small query language.
+///
+/// ```text
+/// type = Weapon and data.chop_max > 30 and name ~ "Daedric"
+/// ```
+///
+/// Fields are addressed by dotted paths following the record's serialized (serde) structure, so
+/// every field of every record type is available. `type` is the record type name, e.g. `Weapon`.
+/// Paths that pass through a list match if any element matches, and list elements can also be
+/// addressed by position, e.g. `effects.0.magic_effect`.
+///
+/// | Syntax               | Meaning                                                    |
+/// |----------------------|------------------------------------------------------------|
+/// | `a = b`, `a != b`    | Equality. Strings compare case-insensitively.              |
+/// | `<`, `<=`, `>`, `>=` | Numeric comparison.                                        |
+/// | `a ~ b`              | The string contains `b`, case-insensitively.               |
+/// | `a`                  | The field is present and not empty, zero or false.         |
+/// | `and`, `or`, `not`   | Logical operators, in increasing order of precedence.      |
+/// | `( ... )`            | Grouping.                                                  |
+///
+/// Values are numbers, `true`, `false`, quoted strings, or bare words (which are strings).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    expr: Expr,
+}
+
+/// A selection of fields to output for each record matched by a [`Query`].
+///
+/// Fields use the same dotted paths as queries.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Projection {
+    pub fields: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Truthy(String),
+    Compare(String, Op, Literal),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Literal {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    String(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl Query {
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Reader::error(format!("Unexpected token in query: {token:?}"));
+        }
+        Ok(Self { expr })
+    }
+
+    pub fn matches(&self, object: &TES3Object) -> bool {
+        let record = Record::new(object);
+        self.expr.eval(&record)
+    }
+}
+
+impl FromStr for Query {
+    type Err = io::Error;
+
+    fn from_str(text: &str) -> io::Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl Projection {
+    pub fn new(fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            fields: fields.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parse a comma separated list of field paths.
+    pub fn parse(text: &str) -> Self {
+        Self::new(text.split(',').map(str::trim).filter(|field| !field.is_empty()))
+    }
+
+    /// The value of each field for `object`, or null where it has none.
+    ///
+    /// Fields matching more than one value, by passing through a list, produce a list.
+    pub fn values(&self, object: &TES3Object) -> Vec<Value> {
+        let record = Record::new(object);
+        self.fields
+            .iter()
+            .map(|field| {
+                let mut values: Vec<_> = record.resolve(field).into_iter().cloned().collect();
+                match values.len() {
+                    0 => Value::Null,
+                    1 => values.remove(0),
+                    _ => Value::Array(values),
+                }
+            })
+            .collect()
+    }
+
+    /// Write one CSV row per object, with a header row of field paths.
+    pub fn save_csv(
+        &self,
+        objects: impl IntoIterator<Item = impl Borrow<TES3Object>>,
+        mut writer: impl Write,
+    ) -> io::Result<()> {
+        let header: Vec<_> = self.fields.iter().map(|field| csv_quote(field)).collect();
+        writeln!(writer, "{}", header.join(","))?;
+
+        for object in objects {
+            let row: Vec<_> = self
+                .values(object.borrow())
+                .iter()
+                .map(|value| match value {
+                    Value::Null => String::new(),
+                    Value::String(text) => csv_quote(text).into_owned(),
+                    _ => csv_quote(&value.to_string()).into_owned(),
+                })
+                .collect();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a JSON array holding one object per record, keyed by field path.
+    pub fn save_json(
+        &self,
+        objects: impl IntoIterator<Item = impl Borrow<TES3Object>>,
+        writer: impl Write,
+    ) -> io::Result<()> {
+        let rows: Vec<_> = objects
+            .into_iter()
+            .map(|object| {
+                let values = self.values(object.borrow());
+                Value::Object(self.fields.iter().cloned().zip(values).collect::<Map<_, _>>())
+            })
+            .collect();
+        serde_json::to_writer_pretty(writer, &rows)?;
+        Ok(())
+    }
+}
+
+impl Plugin {
+    /// All objects matching `query`, in order.
+    pub fn query<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a TES3Object> {
+        self.objects.iter().filter(|object| query.matches(object))
+    }
+}
+
+/// A record being evaluated, serialized only when a field other than `type` is needed.
+struct Record<'a> {
+    object: &'a TES3Object,
+    type_name: Value,
+    value: OnceCell<Value>,
+}
+
+impl<'a> Record<'a> {
+    fn new(object: &'a TES3Object) -> Self {
+        Self {
+            object,
+            type_name: Value::String(object.type_name().into()),
+            value: OnceCell::new(),
+        }
+    }
+
+    fn value(&self) -> &Value {
+        self.value
+            .get_or_init(|| serde_json::to_value(self.object).unwrap_or_default())
+    }
+
+    /// Every value found at `path`, fanning out over lists.
+    fn resolve(&self, path: &str) -> Vec<&Value> {
+        if path == "type" {
+            return vec![&self.type_name];
+        }
+        let mut values = vec![self.value()];
+        for segment in path.split('.') {
+            values = values.into_iter().flat_map(|value| step(value, segment)).collect();
+        }
+        values
+    }
+}
+
+fn step<'v>(value: &'v Value, segment: &str) -> Vec<&'v Value> {
+    match value {
+        Value::Object(map) => map.get(segment).into_iter().collect(),
+        Value::Array(items) => segment.parse::<usize>().map_or_else(
+            |_| items.iter().flat_map(|item| step(item, segment)).collect(),
+            |index| items.get(index).into_iter().collect(),
+        ),
+        _ => vec![],
+    }
+}
+
+impl Op {
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Contains => "~",
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, record: &Record<'_>) -> bool {
+        match self {
+            Self::And(a, b) => a.eval(record) && b.eval(record),
+            Self::Or(a, b) => a.eval(record) || b.eval(record),
+            Self::Not(a) => !a.eval(record),
+            Self::Truthy(path) => record.resolve(path).into_iter().any(is_truthy),
+            Self::Compare(path, Op::Ne, literal) => {
+                !record.resolve(path).into_iter().any(|value| compare(value, Op::Eq, literal))
+            }
+            Self::Compare(path, op, literal) => record.resolve(path).into_iter().any(|value| compare(value, *op, literal)),
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|number| number != 0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn compare(value: &Value, op: Op, literal: &Literal) -> bool {
+    // numbers written as bare words or strings still compare numerically
+    let number = match literal {
+        Literal::Number(number) => Some(*number),
+        Literal::String(text) => text.parse().ok(),
+        Literal::Bool(_) => None,
+    };
+
+    match (value, op) {
+        (Value::Number(value), _) => match (value.as_f64(), number) {
+            (Some(value), Some(number)) => match op {
+                Op::Eq | Op::Ne => (value - number).abs() < f64::EPSILON,
+                Op::Lt => value < number,
+                Op::Le => value <= number,
+                Op::Gt => value > number,
+                Op::Ge => value >= number,
+                Op::Contains => false,
+            },
+            _ => false,
+        },
+        (Value::Bool(value), Op::Eq | Op::Ne) => matches!(literal, Literal::Bool(literal) if literal == value),
+        (Value::String(text), Op::Eq | Op::Ne) => match literal {
+            Literal::String(literal) => text.to_lowercase() == literal.to_lowercase(),
+            Literal::Number(_) => number.is_some_and(|number| text.parse() == Ok(number)),
+            Literal::Bool(literal) => text.parse() == Ok(*literal),
+        },
+        (Value::String(text), Op::Contains) => match literal {
+            Literal::String(literal) => text.to_lowercase().contains(&literal.to_lowercase()),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn tokenize(text: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '=' => Token::Op(Op::Eq),
+            '~' => Token::Op(Op::Contains),
+            '!' | '<' | '>' => {
+                let eq = chars.next_if_eq(&'=').is_some();
+                match (c, eq) {
+                    ('!', true) => Token::Op(Op::Ne),
+                    ('<', false) => Token::Op(Op::Lt),
+                    ('<', true) => Token::Op(Op::Le),
+                    ('>', false) => Token::Op(Op::Gt),
+                    ('>', true) => Token::Op(Op::Ge),
+                    _ => return Reader::error("Expected '=' after '!' in query"),
+                }
+            }
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => string.extend(chars.next()),
+                        Some(c) => string.push(c),
+                        None => return Reader::error("Unterminated string in query"),
+                    }
+                }
+                Token::String(string)
+            }
+            c if is_word_char(c) => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|&c| is_word_char(c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            c => return Reader::error(format!("Unexpected character in query: {c:?}")),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+')
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos)?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.tokens.get(self.pos), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        self.pos += usize::from(found);
+        found
+    }
+
+    fn parse_or(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_not()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> io::Result<Expr> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> io::Result<Expr> {
+        let path = match self.next() {
+            Some(Token::Open) => {
+                let expr = self.parse_or()?;
+                if self.next() != Some(&Token::Close) {
+                    return Reader::error("Expected ')' in query");
+                }
+                return Ok(expr);
+            }
+            Some(Token::Word(path)) => path.clone(),
+            Some(token) => return Reader::error(format!("Expected a field in query, found {token:?}")),
+            None => return Reader::error("Unexpected end of query"),
+        };
+
+        let Some(&Token::Op(op)) = self.tokens.get(self.pos) else {
+            return Ok(Expr::Truthy(path));
+        };
+        self.pos += 1;
+
+        let literal = match self.next() {
+            Some(Token::String(text)) => Literal::String(text.clone()),
+            Some(Token::Word(word)) => match word.as_str() {
+                "true" => Literal::Bool(true),
+                "false" => Literal::Bool(false),
+                _ => word.parse().map_or_else(|_| Literal::String(word.clone()), Literal::Number),
+            },
+            _ => return Reader::error(format!("Expected a value after '{}' in query", op.symbol())),
+        };
+
+        Ok(Expr::Compare(path, op, literal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weapon(id: &str, name: &str, chop_max: u8) -> TES3Object {
+        let mut weapon = Weapon {
+            id: id.into(),
+            name: name.into(),
+            ..default()
+        };
+        weapon.data.chop_max = chop_max;
+        weapon.into()
+    }
+
+    #[test]
+    fn query() {
+        let plugin = Plugin {
+            objects: vec![
+                weapon("daedric_club", "Daedric Club", 35),
+                weapon("iron_club", "Iron Club", 12),
+                weapon("daedric_dagger", "Daedric Dagger", 20),
+                Static {
+                    id: "daedric_statue".into(),
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        };
+
+        let query = Query::parse(r#"type = Weapon and data.chop_max > 30 and name ~ "daedric""#).unwrap();
+        let ids: Vec<_> = plugin.query(&query).map(|object| object.editor_id()).collect();
+        assert_eq!(ids, ["daedric_club"]);
+
+        let query = Query::parse("not (type = weapon or id = IRON_CLUB) or data.chop_max <= 12").unwrap();
+        let ids: Vec<_> = plugin.query(&query).map(|object| object.editor_id()).collect();
+        assert_eq!(ids, ["iron_club", "daedric_statue"]);
+
+        assert!(Query::parse("type =").is_err());
+        assert!(Query::parse("(type = Weapon").is_err());
+    }
+
+    #[test]
+    fn projection() {
+        let objects = [weapon("iron_club", "Iron, Club", 12)];
+        let projection = Projection::parse("type, id, name, data.chop_max, missing");
+
+        let mut csv = vec![];
+        projection.save_csv(&objects, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "type,id,name,data.chop_max,missing\nWeapon,iron_club,\"Iron, Club\",12,\n"
+        );
+
+        let mut json = vec![];
+        projection.save_json(&objects, &mut json).unwrap();
+        let json: Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json[0]["data.chop_max"], 12);
+        assert_eq!(json[0]["missing"], Value::Null);
+    }
+}
@@ -0,0 +1,208 @@
+// internal imports
+use crate::prelude::*;
+
+/// The largest `refr_index` that can be stored, as indices are packed into 24 bits.
+pub const MAX_REFR_INDEX: u32 = 0x00FF_FFFF;
+
+/// A plugin-defined `refr_index` that is used by more than one reference.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefrIndexCollision {
+    pub refr_index: u32,
+    /// The editor ids of the cells containing each colliding reference.
+    pub cells: Vec<String>,
+}
+
+/// A reference whose `refr_index` was changed by [`Plugin::renumber_references`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefrIndexChange {
+    pub cell: String,
+    pub id: String,
+    pub old_index: u32,
+    pub new_index: u32,
+}
+
+/// The result of [`Plugin::renumber_references`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RenumberReport {
+    pub changes: Vec<RefrIndexChange>,
+    /// Collisions that existed before renumbering. These are resolved by the renumber.
+    pub collisions: Vec<RefrIndexCollision>,
+}
+
+impl Plugin {
+    /// The next unused `refr_index` for references defined by this plugin (`mast_index` 0).
+    pub fn next_refr_index(&self) -> io::Result<u32> {
+        let next = self
+            .objects_of_type::<Cell>()
+            .flat_map(|cell| cell.references.keys())
+            .filter(|(mast_index, _)| *mast_index == 0)
+            .map(|(_, refr_index)| refr_index + 1)
+            .max()
+            .unwrap_or(1);
+
+        if next > MAX_REFR_INDEX {
+            return Reader::error("No free reference indices remaining");
+        }
+
+        Ok(next)
+    }
+
+    /// Insert a new reference into the exterior cell at `grid`, assigning it the next free index.
+    ///
+    /// Returns the `(mast_index, refr_index)` key the reference was inserted with.
+    pub fn insert_exterior_reference(&mut self, grid: (i32, i32), reference: Reference) -> io::Result<(u32, u32)> {
+        let refr_index = self.next_refr_index()?;
+        let Some(cell) = self.exterior_cell_mut(grid) else {
+            return Reader::error(format!("Exterior cell not found: {grid:?}"));
+        };
+        Ok(cell.insert_reference(reference, refr_index))
+    }
+
+    /// Insert a new reference into the interior cell named `name`, assigning it the next free index.
+    ///
+    /// Returns the `(mast_index, refr_index)` key the reference was inserted with.
+    pub fn insert_interior_reference(&mut self, name: &str, reference: Reference) -> io::Result<(u32, u32)> {
+        let refr_index = self.next_refr_index()?;
+        let Some(cell) = self.interior_cell_mut(name) else {
+            return Reader::error(format!("Interior cell not found: {name}"));
+        };
+        Ok(cell.insert_reference(reference, refr_index))
+    }
+
+    /// The interior cell record with the given name (case-insensitive).
+    pub fn interior_cell(&self, name: &str) -> Option<&Cell> {
+        self.objects_of_type::<Cell>()
+            .find(|cell| cell.is_interior() && cell.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The interior cell record with the given name (case-insensitive).
+    pub fn interior_cell_mut(&mut self, name: &str) -> Option<&mut Cell> {
+        self.objects_of_type_mut::<Cell>()
+            .find(|cell| cell.is_interior() && cell.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find plugin-defined reference indices that are used in more than one cell.
+    pub fn refr_index_collisions(&self) -> Vec<RefrIndexCollision> {
+        let mut cells_by_index: HashMap<u32, Vec<String>> = HashMap::new();
+
+        for cell in self.objects_of_type::<Cell>() {
+            for (_, refr_index) in cell.references.keys().filter(|(mast_index, _)| *mast_index == 0) {
+                cells_by_index
+                    .entry(*refr_index)
+                    .or_default()
+                    .push(cell.editor_id().into_owned());
+            }
+        }
+
+        let mut collisions: Vec<_> = cells_by_index
+            .into_iter()
+            .filter(|(_, cells)| cells.len() > 1)
+            .map(|(refr_index, cells)| RefrIndexCollision { refr_index, cells })
+            .collect();
+        collisions.sort_unstable_by_key(|collision| collision.refr_index);
+
+        collisions
+    }
+
+    /// Renumber the references defined by this plugin so their indices are dense, starting from 1.
+    ///
+    /// References keep their relative order: cells in plugin order, then by previous index.
+    pub fn renumber_references(&mut self) -> io::Result<RenumberReport> {
+        let mut report = RenumberReport {
+            collisions: self.refr_index_collisions(),
+            ..default()
+        };
+
+        let count = self
+            .objects_of_type::<Cell>()
+            .map(|cell| cell.references.keys().filter(|(mast_index, _)| *mast_index == 0).count())
+            .sum::<usize>();
+        if count > MAX_REFR_INDEX as usize {
+            return Reader::error(format!("Too many references to renumber: {count}"));
+        }
+
+        let mut next_index = 1;
+
+        for cell in self.objects_of_type_mut::<Cell>() {
+            let mut keys: Vec<_> = cell
+                .references
+                .keys()
+                .copied()
+                .filter(|(mast_index, _)| *mast_index == 0)
+                .collect();
+            keys.sort_unstable();
+
+            let mut renumbered = Vec::with_capacity(keys.len());
+            for key in keys {
+                if let Some(reference) = cell.references.remove(&key) {
+                    renumbered.push(reference);
+                }
+            }
+
+            for mut reference in renumbered {
+                let old_index = reference.refr_index;
+                if old_index != next_index {
+                    report.changes.push(RefrIndexChange {
+                        cell: cell.editor_id().into_owned(),
+                        id: reference.id.clone(),
+                        old_index,
+                        new_index: next_index,
+                    });
+                }
+                reference.refr_index = next_index;
+                cell.references.insert((0, next_index), reference);
+                next_index += 1;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl Cell {
+    /// Insert a reference defined by this plugin, overwriting its indices with `(0, refr_index)`.
+    ///
+    /// Use [`Plugin::next_refr_index`] to find an index that is not already taken.
+    pub fn insert_reference(&mut self, mut reference: Reference, refr_index: u32) -> (u32, u32) {
+        reference.mast_index = 0;
+        reference.refr_index = refr_index;
+        self.references.insert((0, refr_index), reference);
+        (0, refr_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interior(name: &str, indices: &[u32]) -> TES3Object {
+        let mut cell = Cell {
+            name: name.into(),
+            ..default()
+        };
+        cell.data.flags.insert(CellFlags::IS_INTERIOR);
+        for &refr_index in indices {
+            cell.insert_reference(default(), refr_index);
+        }
+        cell.into()
+    }
+
+    #[test]
+    fn allocate_and_renumber() {
+        let mut plugin = Plugin {
+            objects: vec![interior("a", &[3, 7]), interior("b", &[7, 10])],
+            ..default()
+        };
+
+        assert_eq!(plugin.next_refr_index().unwrap(), 11);
+        assert_eq!(plugin.insert_interior_reference("A", default()).unwrap(), (0, 11));
+
+        let report = plugin.renumber_references().unwrap();
+        assert_eq!(report.collisions.len(), 1);
+        assert_eq!(report.collisions[0].refr_index, 7);
+        assert_eq!(report.changes.len(), 5);
+
+        assert!(plugin.refr_index_collisions().is_empty());
+        assert_eq!(plugin.next_refr_index().unwrap(), 6);
+    }
+}
@@ -0,0 +1,820 @@
+// internal imports
+use crate::prelude::*;
+use crate::utils::script_text::{rewrite_script_tokens, script_tokens, ScriptName, ScriptToken};
+
+/// A field changed by [`Plugin::rename_id`] or [`Plugin::rename_cell`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenameSite {
+    /// The position of the plugin within the slice given to [`Plugin::rename_id_in`], or 0.
+    pub plugin: usize,
+    /// The tag of the record containing the field, e.g. `NPC_`.
+    pub tag: String,
+    /// The id of the record containing the field, after renaming.
+    pub id: String,
+    /// The field that was changed, e.g. `inventory` or `references.owner`.
+    pub field: &'static str,
+}
+
+/// The result of [`Plugin::rename_id`] or [`Plugin::rename_cell`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RenameReport {
+    pub sites: Vec<RenameSite>,
+    /// Problems the rename could not fix, which may need attention.
+    pub warnings: Vec<String>,
+}
+
+impl RenameReport {
+    /// Sites within script text. Compiled script bytecode is not updated, so the scripts and
+    /// dialogue results they belong to must be recompiled.
+    pub fn script_sites(&self) -> impl Iterator<Item = &RenameSite> {
+        self.sites.iter().filter(|site| matches!(site.field, "text" | "script_text"))
+    }
+}
+
+/// The set of records an id is unique within. Fields only ever refer to one of these.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Namespace {
+    /// Anything that can be placed in a cell or carried, e.g. NPCs, items and leveled lists.
+    Object,
+    BodyPart,
+    Class,
+    Enchanting,
+    Faction,
+    Global,
+    Race,
+    Region,
+    Script,
+    Sound,
+    Spell,
+    Topic,
+    /// Interior cells, which are referred to by name.
+    Cell,
+    /// Records that nothing else refers to by id.
+    Unreferenced([u8; 4]),
+}
+
+impl Namespace {
+    fn of(tag: [u8; 4]) -> Option<Self> {
+        Some(match &tag {
+            b"ACTI" | b"ALCH" | b"APPA" | b"ARMO" | b"BOOK" | b"CLOT" | b"CONT" | b"CREA" | b"DOOR" | b"INGR" | b"LEVC"
+            | b"LEVI" | b"LIGH" | b"LOCK" | b"MISC" | b"NPC_" | b"PROB" | b"REPA" | b"STAT" | b"WEAP" => Self::Object,
+            b"BODY" => Self::BodyPart,
+            b"CLAS" => Self::Class,
+            b"DIAL" => Self::Topic,
+            b"ENCH" => Self::Enchanting,
+            b"FACT" => Self::Faction,
+            b"GLOB" => Self::Global,
+            b"RACE" => Self::Race,
+            b"REGN" => Self::Region,
+            b"SCPT" => Self::Script,
+            b"SOUN" => Self::Sound,
+            b"SPEL" => Self::Spell,
+            b"BSGN" | b"GMST" | b"LTEX" | b"SNDG" | b"SSCR" => Self::Unreferenced(tag),
+            _ => return None,
+        })
+    }
+}
+
+/// Reads the ids a record refers to, through [`visit::object`].
+trait IdVisitor {
+    fn field(&mut self, namespace: Namespace, value: &str, field: &'static str);
+
+    /// A cell name the game matches by prefix, e.g. `Balmora` also matches `Balmora, Caius Cosades' House`.
+    fn cell_prefix(&mut self, value: &str, field: &'static str) {
+        self.field(Namespace::Cell, value, field);
+    }
+
+    fn script(&mut self, text: &str, field: &'static str);
+}
+
+/// Rewrites the ids a record refers to, through [`rewrite::object`].
+trait IdRewriter {
+    fn field(&mut self, namespace: Namespace, value: &mut String, field: &'static str);
+
+    /// A cell name the game matches by prefix, e.g. `Balmora` also matches `Balmora, Caius Cosades' House`.
+    fn cell_prefix(&mut self, value: &mut String, field: &'static str) {
+        self.field(Namespace::Cell, value, field);
+    }
+
+    fn script(&mut self, text: &mut String, field: &'static str);
+}
+
+/// Defines a module walking every field of a record that refers to an id, once for shared and
+/// once for mutable access, so that reading and rewriting ids cannot disagree on the fields.
+macro_rules! id_fields {
+    ($module:ident, $Visitor:ident, $iter:ident, $values:ident, $as_ref:ident $(, $mut:tt)?) => {
+        mod $module {
+            use super::*;
+
+            // the lifetime cannot be elided within `impl Trait` yet
+            #[allow(single_use_lifetimes)]
+            fn fields<'a, V: $Visitor>(
+                visitor: &mut V,
+                namespace: Namespace,
+                values: impl IntoIterator<Item = &'a $($mut)? String>,
+                field: &'static str,
+            ) {
+                for value in values {
+                    visitor.field(namespace, value, field);
+                }
+            }
+
+            fn ai_packages<V: $Visitor>(visitor: &mut V, packages: &$($mut)? [AiPackage]) {
+                for package in packages {
+                    let (target, cell) = match package {
+                        AiPackage::Escort(package) => (&$($mut)? package.target, Some(&$($mut)? package.cell)),
+                        AiPackage::Follow(package) => (&$($mut)? package.target, Some(&$($mut)? package.cell)),
+                        AiPackage::Activate(package) => (&$($mut)? package.target, None),
+                        _ => continue,
+                    };
+                    visitor.field(Namespace::Object, target, "ai_packages");
+                    fields(visitor, Namespace::Cell, cell, "ai_packages");
+                }
+            }
+
+            fn travel_destinations<V: $Visitor>(visitor: &mut V, destinations: &$($mut)? [TravelDestination]) {
+                let cells = destinations.$iter().map(|destination| &$($mut)? destination.cell);
+                fields(visitor, Namespace::Cell, cells, "travel_destinations");
+            }
+
+            fn biped_objects<V: $Visitor>(visitor: &mut V, biped_objects: &$($mut)? [BipedObject]) {
+                for biped_object in biped_objects {
+                    visitor.field(Namespace::BodyPart, &$($mut)? biped_object.male_bodypart, "biped_objects");
+                    visitor.field(Namespace::BodyPart, &$($mut)? biped_object.female_bodypart, "biped_objects");
+                }
+            }
+
+            fn inventory<V: $Visitor>(visitor: &mut V, inventory: &$($mut)? [(i32, FixedString<32>)]) {
+                fields(visitor, Namespace::Object, inventory.$iter().map(|(_, id)| &$($mut)? id.0), "inventory");
+            }
+
+            fn reference<V: $Visitor>(visitor: &mut V, reference: &$($mut)? Reference) {
+                use Namespace::{Cell, Faction, Global, Object, Spell};
+                visitor.field(Object, &$($mut)? reference.id, "references.id");
+                fields(visitor, Object, &$($mut)? reference.owner, "references.owner");
+                fields(visitor, Global, &$($mut)? reference.owner_global, "references.owner_global");
+                fields(visitor, Faction, &$($mut)? reference.owner_faction, "references.owner_faction");
+                fields(visitor, Object, &$($mut)? reference.key, "references.key");
+                fields(visitor, Spell, &$($mut)? reference.trap, "references.trap");
+                fields(visitor, Object, &$($mut)? reference.soul, "references.soul");
+                let destination = reference.destination.$as_ref().map(|destination| &$($mut)? destination.cell);
+                fields(visitor, Cell, destination, "references.destination");
+            }
+
+            fn filter<V: $Visitor>(visitor: &mut V, filter: &$($mut)? Filter) {
+                let namespace = match filter.filter_type {
+                    FilterType::Global => Namespace::Global,
+                    FilterType::Journal => Namespace::Topic,
+                    FilterType::Item | FilterType::Dead | FilterType::NotId => Namespace::Object,
+                    FilterType::NotFaction => Namespace::Faction,
+                    FilterType::NotClass => Namespace::Class,
+                    FilterType::NotRace => Namespace::Race,
+                    FilterType::NotCell => return visitor.cell_prefix(&$($mut)? filter.id, "filters"),
+                    _ => return,
+                };
+                visitor.field(namespace, &$($mut)? filter.id, "filters");
+            }
+
+            /// Rewrite every field of `object` that refers to the renamed id.
+            #[allow(clippy::too_many_lines)]
+            pub(super) fn object<V: $Visitor>(visitor: &mut V, object: &$($mut)? TES3Object) {
+                use Namespace::{BodyPart, Cell, Class, Enchanting, Faction, Object, Race, Region, Script, Sound, Spell};
+
+                match object {
+                    TES3Object::Activator(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Alchemy(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Apparatus(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Armor(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Enchanting, &$($mut)? object.enchanting, "enchanting");
+                        biped_objects(visitor, &$($mut)? object.biped_objects);
+                    }
+                    TES3Object::Birthsign(object) => {
+                        fields(visitor, Spell, &$($mut)? object.spells, "spells");
+                    }
+                    TES3Object::Bodypart(object) => {
+                        visitor.field(Race, &$($mut)? object.race, "race");
+                    }
+                    TES3Object::Book(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Enchanting, &$($mut)? object.enchanting, "enchanting");
+                    }
+                    TES3Object::Cell(object) => {
+                        if object.is_interior() {
+                            visitor.field(Cell, &$($mut)? object.name, "name");
+                        }
+                        fields(visitor, Region, &$($mut)? object.region, "region");
+                        for reference in object.references.$values() {
+                            self::reference(visitor, reference);
+                        }
+                    }
+                    TES3Object::Clothing(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Enchanting, &$($mut)? object.enchanting, "enchanting");
+                        biped_objects(visitor, &$($mut)? object.biped_objects);
+                    }
+                    TES3Object::Container(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        inventory(visitor, &$($mut)? object.inventory);
+                    }
+                    TES3Object::Creature(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        inventory(visitor, &$($mut)? object.inventory);
+                        fields(visitor, Spell, &$($mut)? object.spells, "spells");
+                        ai_packages(visitor, &$($mut)? object.ai_packages);
+                        travel_destinations(visitor, &$($mut)? object.travel_destinations);
+                        visitor.field(Object, &$($mut)? object.sound, "sound");
+                    }
+                    TES3Object::DialogueInfo(object) => {
+                        visitor.field(Object, &$($mut)? object.speaker_id, "speaker_id");
+                        visitor.field(Race, &$($mut)? object.speaker_race, "speaker_race");
+                        visitor.field(Class, &$($mut)? object.speaker_class, "speaker_class");
+                        visitor.field(Faction, &$($mut)? object.speaker_faction, "speaker_faction");
+                        visitor.field(Faction, &$($mut)? object.player_faction, "player_faction");
+                        visitor.cell_prefix(&$($mut)? object.speaker_cell, "speaker_cell");
+                        for filter in &$($mut)? object.filters {
+                            self::filter(visitor, filter);
+                        }
+                        visitor.script(&$($mut)? object.script_text, "script_text");
+                    }
+                    TES3Object::Door(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Sound, &$($mut)? object.open_sound, "open_sound");
+                        visitor.field(Sound, &$($mut)? object.close_sound, "close_sound");
+                    }
+                    TES3Object::Faction(object) => {
+                        let reactions = object.reactions.$iter().map(|reaction| &$($mut)? reaction.faction);
+                        fields(visitor, Faction, reactions, "reactions");
+                    }
+                    TES3Object::Ingredient(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::LeveledCreature(object) => {
+                        fields(visitor, Object, object.creatures.$iter().map(|(id, _)| id), "creatures");
+                    }
+                    TES3Object::LeveledItem(object) => {
+                        fields(visitor, Object, object.items.$iter().map(|(id, _)| id), "items");
+                    }
+                    TES3Object::Light(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Sound, &$($mut)? object.sound, "sound");
+                    }
+                    TES3Object::Lockpick(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::MagicEffect(object) => {
+                        visitor.field(Sound, &$($mut)? object.bolt_sound, "bolt_sound");
+                        visitor.field(Sound, &$($mut)? object.cast_sound, "cast_sound");
+                        visitor.field(Sound, &$($mut)? object.hit_sound, "hit_sound");
+                        visitor.field(Sound, &$($mut)? object.area_sound, "area_sound");
+                        visitor.field(Object, &$($mut)? object.cast_visual, "cast_visual");
+                        visitor.field(Object, &$($mut)? object.bolt_visual, "bolt_visual");
+                        visitor.field(Object, &$($mut)? object.hit_visual, "hit_visual");
+                        visitor.field(Object, &$($mut)? object.area_visual, "area_visual");
+                    }
+                    TES3Object::MiscItem(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Npc(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        inventory(visitor, &$($mut)? object.inventory);
+                        fields(visitor, Spell, &$($mut)? object.spells, "spells");
+                        ai_packages(visitor, &$($mut)? object.ai_packages);
+                        travel_destinations(visitor, &$($mut)? object.travel_destinations);
+                        visitor.field(Race, &$($mut)? object.race, "race");
+                        visitor.field(Class, &$($mut)? object.class, "class");
+                        visitor.field(Faction, &$($mut)? object.faction, "faction");
+                        visitor.field(BodyPart, &$($mut)? object.head, "head");
+                        visitor.field(BodyPart, &$($mut)? object.hair, "hair");
+                    }
+                    TES3Object::PathGrid(object) => {
+                        visitor.field(Cell, &$($mut)? object.cell, "cell");
+                    }
+                    TES3Object::Probe(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Race(object) => {
+                        fields(visitor, Spell, &$($mut)? object.spells, "spells");
+                    }
+                    TES3Object::Region(object) => {
+                        visitor.field(Object, &$($mut)? object.sleep_creature, "sleep_creature");
+                        fields(visitor, Sound, object.sounds.$iter().map(|(id, _)| &$($mut)? id.0), "sounds");
+                    }
+                    TES3Object::RepairItem(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Script(object) => {
+                        visitor.script(&$($mut)? object.text, "text");
+                    }
+                    TES3Object::SoundGen(object) => {
+                        visitor.field(Object, &$($mut)? object.creature, "creature");
+                        visitor.field(Sound, &$($mut)? object.sound, "sound");
+                    }
+                    TES3Object::StartScript(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                    }
+                    TES3Object::Weapon(object) => {
+                        visitor.field(Script, &$($mut)? object.script, "script");
+                        visitor.field(Enchanting, &$($mut)? object.enchanting, "enchanting");
+                    }
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+id_fields!(visit, IdVisitor, iter, values, as_ref);
+id_fields!(rewrite, IdRewriter, iter_mut, values_mut, as_mut, mut);
+
+/// Renames one id within a single object, remembering which fields were changed.
+struct Renamer<'a> {
+    namespace: Namespace,
+    old: &'a str,
+    new: &'a str,
+    fields: Vec<&'static str>,
+    warnings: Vec<String>,
+}
+
+impl IdRewriter for Renamer<'_> {
+    fn field(&mut self, namespace: Namespace, value: &mut String, field: &'static str) {
+        if namespace == self.namespace && value.eq_ignore_ascii_case(self.old) {
+            self.new.clone_into(value);
+            self.fields.push(field);
+        }
+    }
+
+    fn cell_prefix(&mut self, value: &mut String, field: &'static str) {
+        let renamed = self.fields.len();
+        self.field(Namespace::Cell, value, field);
+        if self.fields.len() > renamed {
+            return;
+        }
+
+        let is_prefix_of = |name: &str| name.get(..value.len()).is_some_and(|name| name.eq_ignore_ascii_case(value));
+        if self.namespace == Namespace::Cell && is_prefix_of(self.old) && !is_prefix_of(self.new) {
+            self.warnings.push(format!(
+                "{field} \"{value}\" matched \"{}\" by prefix, but does not match \"{}\"",
+                self.old, self.new
+            ));
+        }
+    }
+
+    fn script(&mut self, text: &mut String, field: &'static str) {
+        let rewritten = rewrite_script_tokens(text, |kind, token| {
+            let name = match token {
+                ScriptToken::Quoted(name) | ScriptToken::Word(name) => name,
+            };
+            if !name.eq_ignore_ascii_case(self.old) {
+                return None;
+            }
+            let matches = match self.namespace {
+                Namespace::Topic => kind == ScriptName::Topic,
+                Namespace::Cell => kind == ScriptName::Cell,
+                Namespace::Unreferenced(_) => false,
+                _ => kind == ScriptName::Other,
+            };
+            matches.then(|| self.new.to_owned())
+        });
+        if let Some(rewritten) = rewritten {
+            *text = rewritten;
+            self.fields.push(field);
+        }
+    }
+}
+
+/// Collects every id referred to, lowercase, whatever kind of record it names.
+#[derive(Default)]
+struct ReferencedIds(HashSet<String>);
+
+impl IdVisitor for ReferencedIds {
+    fn field(&mut self, _namespace: Namespace, value: &str, _field: &'static str) {
+        if !value.is_empty() {
+            self.0.insert(value.to_ascii_lowercase());
+        }
+    }
+
+    fn script(&mut self, text: &str, _field: &'static str) {
+        script_tokens(text, |_, token| {
+            let (ScriptToken::Quoted(name) | ScriptToken::Word(name)) = token;
+            self.0.insert(name.to_ascii_lowercase());
+        });
+    }
+}
+
+impl Plugin {
+    /// The lowercase ids this plugin refers to from any field, including script text, whatever
+    /// kind of record they name.
+    pub(crate) fn referenced_ids(&self) -> HashSet<String> {
+        let mut referenced = ReferencedIds::default();
+        for object in &self.objects {
+            visit::object(&mut referenced, object);
+        }
+        referenced.0
+    }
+
+    /// Rename the record of type `T` with id `old` (ignoring ascii case) to `new`, and rewrite
+    /// every field that refers to it.
+    ///
+    /// References are rewritten even if the record itself is not in this plugin, so that a plugin
+    /// can be updated after a record in one of its masters was renamed. Script text is updated,
+    /// but compiled script bytecode is not; see [`RenameReport::script_sites`].
+    ///
+    /// Fails if `T` cannot be renamed by id, or if another record already uses `new`.
+    pub fn rename_id<T: RecordType>(&mut self, old: &str, new: &str) -> io::Result<RenameReport> {
+        Self::rename_id_in::<T>(std::slice::from_mut(self), old, new)
+    }
+
+    /// As [`Plugin::rename_id`], for a set of plugins that depend on each other, such as a master
+    /// and the plugins that use it. Nothing is changed unless `new` is unused in every plugin.
+    pub fn rename_id_in<T: RecordType>(plugins: &mut [Self], old: &str, new: &str) -> io::Result<RenameReport> {
+        let tag = T::TAG;
+        let Some(namespace) = Namespace::of(*tag) else {
+            let hint = if tag == Cell::TAG { ", use rename_cell instead" } else { "" };
+            return Reader::error(format!("{} records cannot be renamed by id{hint}", tag.to_str_lossy()));
+        };
+        if new.is_empty() {
+            return Reader::error("Cannot rename to an empty id");
+        }
+        // these ids are stored in fixed length fields, which would truncate them
+        let fixed_length = matches!(
+            namespace,
+            Namespace::Object | Namespace::Script | Namespace::Spell | Namespace::Sound
+        );
+        if fixed_length && !plugins.iter().all(|plugin| plugin.encoding().fits_id_length(new)) {
+            return Reader::error(format!("Ids cannot be longer than {MAX_ID_LENGTH} bytes: {new}"));
+        }
+
+        // renaming that only changes case does not conflict with the record itself
+        if !new.eq_ignore_ascii_case(old) {
+            let conflict = plugins
+                .iter()
+                .flat_map(|plugin| &plugin.objects)
+                .filter(|object| Namespace::of(*object.tag()) == Some(namespace))
+                .find(|object| object.editor_id().eq_ignore_ascii_case(new));
+            if let Some(object) = conflict {
+                return Reader::error(format!("The id {new} is already used by a {} record", object.tag_str()));
+            }
+        }
+
+        Ok(rename_in(plugins, namespace, Some(tag), old, new))
+    }
+
+    /// Rename the interior cell `old` (ignoring ascii case) to `new`, and rewrite every field that
+    /// refers to it.
+    ///
+    /// Covers door destinations, actor travel destinations and AI packages, path grids, dialogue
+    /// info speaker cells and `NotCell` filters, and script text. Compiled script bytecode is not
+    /// updated; see [`RenameReport::script_sites`].
+    ///
+    /// Warns if the cell may belong to a master, as the master's cell keeps its old name, and about
+    /// dialogue conditions that matched the old name by prefix but no longer match. Masters are
+    /// not loaded, so any plugin with masters in its header may be overriding a master's cell.
+    pub fn rename_cell(&mut self, old: &str, new: &str) -> io::Result<RenameReport> {
+        Self::rename_cell_in(std::slice::from_mut(self), old, new)
+    }
+
+    /// As [`Plugin::rename_cell`], for a set of plugins that depend on each other, such as a
+    /// master and the plugins that use it. Nothing is changed unless `new` is unused in every
+    /// plugin.
+    pub fn rename_cell_in(plugins: &mut [Self], old: &str, new: &str) -> io::Result<RenameReport> {
+        if new.is_empty() {
+            return Reader::error("Cannot rename to an empty cell name");
+        }
+
+        let cells = || plugins.iter().flat_map(Self::objects_of_type::<Cell>);
+        if cells().any(|cell| cell.is_exterior() && cell.name.eq_ignore_ascii_case(old)) {
+            return Reader::error(format!("Only interior cells can be renamed: {old}"));
+        }
+        if !new.eq_ignore_ascii_case(old) && cells().any(|cell| cell.name.eq_ignore_ascii_case(new)) {
+            return Reader::error(format!("The cell name {new} is already used"));
+        }
+
+        // a cell is only known to be owned by the set if a plugin without masters defines it,
+        // otherwise one of the masters may define it too
+        let owned = plugins.iter().any(|plugin| {
+            plugin.header().is_none_or(|header| header.masters.is_empty())
+                && plugin
+                    .objects_of_type::<Cell>()
+                    .any(|cell| cell.name.eq_ignore_ascii_case(old))
+        });
+
+        let mut report = rename_in(plugins, Namespace::Cell, None, old, new);
+        if !owned {
+            report.warnings.insert(
+                0,
+                format!("Cell \"{old}\" may be defined by a master, where it keeps its old name and would be orphaned"),
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+/// Rename `old` to `new` within `namespace`, including the ids of records tagged `tag`.
+fn rename_in(plugins: &mut [Plugin], namespace: Namespace, tag: Option<&[u8; 4]>, old: &str, new: &str) -> RenameReport {
+    let mut report = RenameReport::default();
+
+    for (i, plugin) in plugins.iter_mut().enumerate() {
+        for object in &mut plugin.objects {
+            let mut renamer = Renamer {
+                namespace,
+                old,
+                new,
+                fields: vec![],
+                warnings: vec![],
+            };
+            if Some(object.tag()) == tag {
+                if let Some(id) = id_mut(object) {
+                    renamer.field(namespace, id, "id");
+                }
+            }
+            rewrite::object(&mut renamer, object);
+
+            for field in renamer.fields {
+                report.sites.push(RenameSite {
+                    plugin: i,
+                    tag: object.tag_str().to_owned(),
+                    id: object.editor_id().into_owned(),
+                    field,
+                });
+            }
+            for warning in renamer.warnings {
+                report
+                    .warnings
+                    .push(format!("{} {}: {warning}", object.tag_str(), object.editor_id()));
+            }
+        }
+
+        if plugin.index.is_some() {
+            plugin.build_index();
+        }
+    }
+
+    report
+}
+
+/// The id field of records that have one.
+fn id_mut(object: &mut TES3Object) -> Option<&mut String> {
+    macro_rules! id_mut {
+        ($($T:ident)*) => {
+            match object {
+                $(TES3Object::$T(object) => Some(&mut object.id),)*
+                _ => None,
+            }
+        };
+    }
+    id_mut!(
+        Activator Alchemy Apparatus Armor Birthsign Bodypart Book Class Clothing Container Creature
+        Dialogue Door Enchanting Faction GameSetting GlobalVariable Ingredient LandscapeTexture
+        LeveledCreature LeveledItem Light Lockpick MiscItem Npc Probe Race Region RepairItem Script
+        Sound SoundGen Spell StartScript Static Weapon
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin() -> Plugin {
+        let mut cell = Cell {
+            name: "Seyda Neen, Fargoth's House".into(),
+            ..default()
+        };
+        cell.references.insert(
+            (0, 1),
+            Reference {
+                refr_index: 1,
+                id: "Fargoth".into(),
+                ..default()
+            },
+        );
+        cell.references.insert(
+            (0, 2),
+            Reference {
+                refr_index: 2,
+                id: "chest_small_01".into(),
+                owner: Some("fargoth".into()),
+                ..default()
+            },
+        );
+
+        Plugin {
+            objects: vec![
+                Npc {
+                    id: "fargoth".into(),
+                    name: "Fargoth".into(),
+                    ..default()
+                }
+                .into(),
+                LeveledCreature {
+                    id: "lev_fargoths".into(),
+                    creatures: vec![("FARGOTH".into(), 1)],
+                    ..default()
+                }
+                .into(),
+                cell.into(),
+                DialogueInfo {
+                    id: "1".into(),
+                    speaker_id: "fargoth".into(),
+                    filters: vec![Filter {
+                        filter_type: FilterType::Dead,
+                        id: "fargoth".into(),
+                        ..default()
+                    }],
+                    script_text: "\"fargoth\"->AddItem \"gold_001\" 5\nAddTopic fargoth".into(),
+                    ..default()
+                }
+                .into(),
+                Script {
+                    id: "fargothScript".into(),
+                    text: "Begin fargothScript\n; fargoth\nif ( fargoth->GetHealth < 1 )\nendif\nEnd".into(),
+                    ..default()
+                }
+                .into(),
+                Faction {
+                    id: "fargoth".into(),
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn rename_id() {
+        let mut plugin = plugin();
+        plugin.objects.push(
+            Script {
+                id: "localScript".into(),
+                text: "Begin localScript\nshort Fargoth\nset fargoth to 1\nEnd".into(),
+                ..default()
+            }
+            .into(),
+        );
+        let report = plugin.rename_id::<Npc>("Fargoth", "fargoth_new").unwrap();
+
+        // references are visited in no particular order
+        let sites: HashSet<_> = report.sites.iter().map(|site| (&*site.tag, site.field)).collect();
+        assert_eq!(
+            sites,
+            HashSet::from([
+                ("NPC_", "id"),
+                ("LEVC", "creatures"),
+                ("CELL", "references.id"),
+                ("CELL", "references.owner"),
+                ("INFO", "speaker_id"),
+                ("INFO", "filters"),
+                ("INFO", "script_text"),
+                ("SCPT", "text"),
+            ])
+        );
+        assert_eq!(report.script_sites().count(), 2);
+
+        let info = plugin.objects_of_type::<DialogueInfo>().next().unwrap();
+        // topics share their name, but are not the same record
+        assert_eq!(info.script_text, "\"fargoth_new\"->AddItem \"gold_001\" 5\nAddTopic fargoth");
+        let script = plugin.objects_of_type::<Script>().next().unwrap();
+        assert_eq!(
+            script.text,
+            "Begin fargothScript\n; fargoth\nif ( fargoth_new->GetHealth < 1 )\nendif\nEnd"
+        );
+        // local variables with the same name are untouched
+        let script = plugin.objects_of_type::<Script>().last().unwrap();
+        assert_eq!(script.text, "Begin localScript\nshort Fargoth\nset fargoth to 1\nEnd");
+        // the faction with the same id is untouched
+        assert_eq!(plugin.objects_of_type::<Faction>().next().unwrap().id, "fargoth");
+    }
+
+    #[test]
+    fn referenced_ids() {
+        let mut plugin = plugin();
+        plugin.objects.push(
+            Script {
+                id: "localScript".into(),
+                text: "Begin localScript\nshort Fargoth\nset fargoth to 1\nEnd".into(),
+                ..default()
+            }
+            .into(),
+        );
+
+        // keywords, numbers, local variables and comments are not ids
+        let mut referenced: Vec<_> = plugin.referenced_ids().into_iter().collect();
+        referenced.sort_unstable();
+        assert_eq!(
+            referenced,
+            [
+                "additem",
+                "chest_small_01",
+                "fargoth",
+                "fargothscript",
+                "gethealth",
+                "gold_001",
+                "localscript"
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_cell() {
+        let mut plugin = plugin();
+        let cell = plugin.objects_of_type_mut::<Cell>().next().unwrap();
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        cell.references.get_mut(&(0, 2)).unwrap().destination = Some(TravelDestination {
+            cell: "seyda neen, fargoth's house".into(),
+            ..default()
+        });
+        plugin.objects.push(
+            DialogueInfo {
+                id: "2".into(),
+                speaker_cell: "Seyda Neen".into(),
+                filters: vec![Filter {
+                    filter_type: FilterType::NotCell,
+                    id: "Seyda Neen, Fargoth's House".into(),
+                    ..default()
+                }],
+                script_text: "PositionCell 0 0 0 0 \"Seyda Neen, Fargoth's House\"".into(),
+                ..default()
+            }
+            .into(),
+        );
+
+        let report = plugin.rename_cell("Seyda Neen, Fargoth's House", "Fargoth's House").unwrap();
+
+        let sites: HashSet<_> = report.sites.iter().map(|site| (&*site.tag, site.field)).collect();
+        assert_eq!(
+            sites,
+            HashSet::from([
+                ("CELL", "name"),
+                ("CELL", "references.destination"),
+                ("INFO", "filters"),
+                ("INFO", "script_text"),
+            ])
+        );
+        assert_eq!(
+            report.warnings,
+            ["INFO 2: speaker_cell \"Seyda Neen\" matched \"Seyda Neen, Fargoth's House\" by prefix, but does not match \"Fargoth's House\""]
+        );
+
+        // cells of plugins with masters may belong to a master, even if they only add references
+        let header = Header {
+            masters: vec![("Morrowind.esm".into(), 0)],
+            ..default()
+        };
+        plugin.objects.insert(0, header.into());
+        let report = plugin.rename_cell("Fargoth's House", "Fargoth's Home").unwrap();
+        assert!(report.warnings[0].contains("may be defined by a master"));
+
+        // as do empty overrides, unless the set includes a plugin without masters defining the cell
+        let cell = plugin.objects_of_type_mut::<Cell>().next().unwrap();
+        cell.references.clear();
+        let mut master = Plugin::new();
+        master.objects.push(
+            Cell {
+                name: "Fargoth's Hut".into(),
+                data: CellData {
+                    flags: CellFlags::IS_INTERIOR,
+                    ..default()
+                },
+                ..default()
+            }
+            .into(),
+        );
+        let mut plugins = [plugin];
+        let report = Plugin::rename_cell_in(&mut plugins, "Fargoth's Home", "Fargoth's Hut").unwrap();
+        assert!(report.warnings[0].contains("may be defined by a master"));
+        let mut plugins = [master, plugins.into_iter().next().unwrap()];
+        let report = Plugin::rename_cell_in(&mut plugins, "Fargoth's Hut", "Fargoth's Shack").unwrap();
+        assert!(report.warnings.iter().all(|warning| !warning.contains("master")));
+    }
+
+    #[test]
+    fn rename_id_conflict() {
+        let mut plugin = plugin();
+        assert!(plugin.rename_id::<Npc>("fargoth", "lev_fargoths").is_err());
+        assert!(plugin
+            .rename_id::<Cell>("Seyda Neen, Fargoth's House", "Fargoth's House")
+            .is_err());
+        // ids must fit fixed length fields with a terminating nul
+        assert!(plugin.rename_id::<Npc>("fargoth", &"a".repeat(MAX_ID_LENGTH + 1)).is_err());
+        assert!(plugin.rename_id::<Npc>("fargoth", &"a".repeat(MAX_ID_LENGTH)).is_ok());
+        assert!(plugin.rename_id::<Npc>(&"a".repeat(MAX_ID_LENGTH), "fargoth").is_ok());
+        assert!(plugin
+            .rename_id::<Spell>("fire bite", &"a".repeat(MAX_ID_LENGTH + 1))
+            .is_err());
+        assert!(plugin.rename_id::<Sound>("fire", &"a".repeat(MAX_ID_LENGTH + 1)).is_err());
+        // ids are measured in the plugin's encoding, where accented letters take a single byte
+        assert!(plugin.rename_id::<Npc>("fargoth", &"é".repeat(MAX_ID_LENGTH)).is_ok());
+        assert!(plugin.rename_id::<Npc>(&"é".repeat(MAX_ID_LENGTH), "fargoth").is_ok());
+        // changing case only is allowed
+        assert!(plugin.rename_id::<Npc>("fargoth", "Fargoth").is_ok());
+        assert_eq!(plugin.get::<Npc>("fargoth").unwrap().id, "Fargoth");
+    }
+}
@@ -0,0 +1,236 @@
+// internal imports
+use crate::prelude::*;
+
+/// Script functions that take a cell name, with the position of that argument (lowercase).
+pub const CELL_FUNCTIONS: &[(&str, usize)] = &[
+    ("aiescortcell", 1),
+    ("aifollowcell", 1),
+    ("centeroncell", 0),
+    ("coc", 0),
+    ("getpccell", 0),
+    ("placeitemcell", 1),
+    ("positioncell", 4),
+    ("showmap", 0),
+];
+
+/// Script functions that take a topic or journal id, with the position of that argument (lowercase).
+pub const TOPIC_FUNCTIONS: &[(&str, usize)] = &[
+    ("addtopic", 0),
+    ("getjournalindex", 0),
+    ("journal", 0),
+    ("setjournalindex", 0),
+];
+
+/// Words of the script language itself, which never name a record (lowercase).
+const KEYWORDS: &[&str] = &[
+    "begin", "else", "elseif", "end", "endif", "endwhile", "float", "if", "long", "return", "set", "short", "to", "while",
+];
+
+/// A token of script source that may refer to a record by name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScriptToken<'a> {
+    /// The contents of a quoted string, without quotes.
+    Quoted(&'a str),
+    /// A bare word that is not a number.
+    Word(&'a str),
+}
+
+/// What a [`ScriptToken`] names, judging by the function it is passed to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScriptName {
+    /// The cell argument of one of [`CELL_FUNCTIONS`].
+    Cell,
+    /// The topic argument of one of [`TOPIC_FUNCTIONS`].
+    Topic,
+    /// Anything else, such as an object id or a function name.
+    Other,
+}
+
+/// The kind of name passed as argument `position` of `function`.
+fn script_name(function: Option<(&str, usize)>) -> ScriptName {
+    let Some((function, position)) = function else {
+        return ScriptName::Other;
+    };
+    let is_argument_of = |functions: &[(&str, usize)]| {
+        functions
+            .iter()
+            .any(|(name, argument)| function.eq_ignore_ascii_case(name) && position == *argument)
+    };
+    if is_argument_of(CELL_FUNCTIONS) {
+        ScriptName::Cell
+    } else if is_argument_of(TOPIC_FUNCTIONS) {
+        ScriptName::Topic
+    } else {
+        ScriptName::Other
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+fn is_known_function(word: &str) -> bool {
+    (CELL_FUNCTIONS.iter().chain(TOPIC_FUNCTIONS)).any(|(name, _)| word.eq_ignore_ascii_case(name))
+}
+
+/// The lowercase names of the local variables declared in script source.
+fn local_variables(text: &str) -> HashSet<String> {
+    let mut variables = HashSet::new();
+    for line in text.lines() {
+        let line = line.split(';').next().unwrap_or_default();
+        let mut words = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty());
+        if let (Some(kind), Some(name)) = (words.next(), words.next()) {
+            if ["short", "long", "float"]
+                .iter()
+                .any(|declaration| kind.eq_ignore_ascii_case(declaration))
+            {
+                variables.insert(name.to_ascii_lowercase());
+            }
+        }
+    }
+    variables
+}
+
+/// Rewrite quoted strings and bare words in script source. Comments are left untouched.
+///
+/// The callback receives what each token names, found by counting the arguments after the
+/// functions of [`CELL_FUNCTIONS`] and [`TOPIC_FUNCTIONS`], along with the token itself. Bare words
+/// that are keywords, such as `set` and `to`, or that name local variables declared with `short`,
+/// `long` or `float` are never passed to it. Returns `None` if nothing was replaced.
+pub fn rewrite_script_tokens(
+    text: &str,
+    mut rewrite: impl FnMut(ScriptName, ScriptToken<'_>) -> Option<String>,
+) -> Option<String> {
+    let locals = local_variables(text);
+    let mut output = String::with_capacity(text.len());
+    let mut changed = false;
+    // the known function being called on this line, and the position of the next argument
+    let mut function: Option<(&str, usize)> = None;
+    let next_argument = |function: &mut Option<(&str, usize)>| {
+        let current = *function;
+        if let Some((_, position)) = function {
+            *position += 1;
+        }
+        script_name(current)
+    };
+
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            function = None;
+            output.push(c);
+            rest = &rest[1..];
+        } else if c == ';' {
+            // comments run until the end of the line
+            let end = rest.find('\n').unwrap_or(rest.len());
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+        } else if c == '"' {
+            let end = rest[1..].find(['"', '\n']).map_or(rest.len(), |i| i + 1);
+            let contents = &rest[1..end];
+            let closed = rest[end..].starts_with('"');
+            output.push('"');
+            if let Some(replacement) = rewrite(next_argument(&mut function), ScriptToken::Quoted(contents)) {
+                changed = true;
+                output.push_str(&replacement);
+                output.push('"');
+            } else {
+                output.push_str(contents);
+                if closed {
+                    output.push('"');
+                }
+            }
+            rest = &rest[end + usize::from(closed)..];
+        } else if is_word_char(c) {
+            let end = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            if word.parse::<f64>().is_ok() || locals.contains(&word.to_ascii_lowercase()) {
+                next_argument(&mut function);
+                output.push_str(word);
+            } else if is_keyword(word) {
+                output.push_str(word);
+            } else if is_known_function(word) {
+                function = Some((word, 0));
+                output.push_str(word);
+            } else {
+                match rewrite(next_argument(&mut function), ScriptToken::Word(word)) {
+                    Some(replacement) if replacement.chars().all(is_word_char) => {
+                        changed = true;
+                        output.push_str(&replacement);
+                    }
+                    Some(replacement) => {
+                        changed = true;
+                        output.push('"');
+                        output.push_str(&replacement);
+                        output.push('"');
+                    }
+                    None => output.push_str(word),
+                }
+            }
+            rest = &rest[end..];
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    changed.then_some(output)
+}
+
+/// Visit the quoted strings and bare words of script source, as [`rewrite_script_tokens`] would
+/// pass them, without rewriting anything.
+pub fn script_tokens(text: &str, mut visit: impl FnMut(ScriptName, ScriptToken<'_>)) {
+    rewrite_script_tokens(text, |name, token| {
+        visit(name, token);
+        None
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_tokens() {
+        let text = concat!(
+            "player->AddTopic \"old topic\"\n",
+            "; \"old topic\" in a comment\n",
+            "AddTopic Old\n",
+            "PositionCell 0 0 0 0 \"old topic\"\n",
+            "AiEscortCell \"old topic\" \"old topic\" 0 0 0 0\n",
+            "AiFollowCell, old, \"old topic\", 0, 0, 0, 0",
+        );
+        let rewritten = rewrite_script_tokens(text, |name, token| match (name, token) {
+            (ScriptName::Topic, ScriptToken::Quoted("old topic")) => Some("new topic".into()),
+            (ScriptName::Topic, ScriptToken::Word("Old")) => Some("New Name".into()),
+            (ScriptName::Cell, ScriptToken::Quoted("old topic")) => Some("new cell".into()),
+            (ScriptName::Other, ScriptToken::Quoted("old topic")) => Some("new npc".into()),
+            (ScriptName::Other, ScriptToken::Word("old")) => Some("new_npc".into()),
+            _ => None,
+        });
+        assert_eq!(
+            rewritten.as_deref(),
+            Some(concat!(
+                "player->AddTopic \"new topic\"\n",
+                "; \"old topic\" in a comment\n",
+                "AddTopic \"New Name\"\n",
+                "PositionCell 0 0 0 0 \"new cell\"\n",
+                "AiEscortCell \"new npc\" \"new cell\" 0 0 0 0\n",
+                "AiFollowCell, new_npc, \"new cell\", 0, 0, 0, 0",
+            ))
+        );
+    }
+
+    #[test]
+    fn skip_keywords_and_locals() {
+        let text =
+            "Begin test\nshort count\nif ( count < 3 )\n    set count to count + 1\nelse\n    Return\nendif\nEnd test";
+        let mut words = vec![];
+        script_tokens(text, |_, token| words.push(format!("{token:?}")));
+        assert_eq!(words, [r#"Word("test")"#, r#"Word("test")"#]);
+    }
+}
@@ -0,0 +1,256 @@
+// external imports
+use glam::Vec3;
+
+// internal imports
+use crate::prelude::*;
+
+/// The size of an exterior cell, in world units.
+pub const CELL_SIZE: f32 = 8192.0;
+
+/// The exterior cell containing the given world position.
+#[allow(clippy::cast_possible_truncation)]
+pub fn exterior_grid_of(position: [f32; 3]) -> (i32, i32) {
+    let [x, y, _] = position;
+    ((x / CELL_SIZE).floor() as i32, (y / CELL_SIZE).floor() as i32)
+}
+
+/// A reference found by [`SpatialIndex`], along with the cell it is listed in.
+#[derive(Clone, Copy, Debug)]
+pub struct SpatialEntry<'a> {
+    pub cell: &'a Cell,
+    pub reference: &'a Reference,
+}
+
+impl SpatialEntry<'_> {
+    pub fn position(&self) -> Vec3 {
+        self.reference.translation.into()
+    }
+}
+
+/// A spatial index over the exterior references of one or more plugins.
+///
+/// References are bucketed on the XY plane. Deleted references are not indexed.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<'a> {
+    entries: Vec<SpatialEntry<'a>>,
+    buckets: HashMap<(i32, i32), Vec<usize>>,
+    bucket_size: f32,
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Index all exterior references, using one bucket per exterior cell.
+    pub fn new(plugins: impl IntoIterator<Item = &'a Plugin>) -> Self {
+        Self::with_bucket_size(plugins, CELL_SIZE)
+    }
+
+    /// Index all exterior references, using buckets of the given size.
+    pub fn with_bucket_size(plugins: impl IntoIterator<Item = &'a Plugin>, bucket_size: f32) -> Self {
+        let mut this = Self {
+            entries: vec![],
+            buckets: HashMap::new(),
+            bucket_size,
+        };
+
+        for plugin in plugins {
+            for cell in plugin.objects_of_type::<Cell>().filter(|cell| cell.is_exterior()) {
+                for reference in cell.references.values().filter(|reference| !reference.deleted()) {
+                    let bucket = this.bucket(reference.translation.into());
+                    this.buckets.entry(bucket).or_default().push(this.entries.len());
+                    this.entries.push(SpatialEntry { cell, reference });
+                }
+            }
+        }
+
+        this
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SpatialEntry<'a>> {
+        self.entries.iter()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn bucket(&self, position: Vec3) -> (i32, i32) {
+        let position = (position / self.bucket_size).floor();
+        (position.x as i32, position.y as i32)
+    }
+
+    fn entries_in_buckets(&self, min: (i32, i32), max: (i32, i32)) -> impl Iterator<Item = &SpatialEntry<'a>> {
+        (min.1..=max.1)
+            .flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+            .filter_map(|bucket| self.buckets.get(&bucket))
+            .flatten()
+            .map(|&i| &self.entries[i])
+    }
+
+    /// References within `radius` units of `center`.
+    pub fn within_radius(&self, center: [f32; 3], radius: f32) -> impl Iterator<Item = &SpatialEntry<'a>> {
+        let center = Vec3::from(center);
+        let min = self.bucket(center - radius);
+        let max = self.bucket(center + radius);
+        self.entries_in_buckets(min, max)
+            .filter(move |entry| entry.position().distance_squared(center) <= radius * radius)
+    }
+
+    /// References inside the axis-aligned box from `min` to `max`, inclusive.
+    pub fn within_box(&self, min: [f32; 3], max: [f32; 3]) -> impl Iterator<Item = &SpatialEntry<'a>> {
+        let (min, max) = (Vec3::from(min), Vec3::from(max));
+        self.entries_in_buckets(self.bucket(min), self.bucket(max))
+            .filter(move |entry| {
+                let position = entry.position();
+                position.cmpge(min).all() && position.cmple(max).all()
+            })
+    }
+
+    /// The `count` references closest to `point`, nearest first.
+    pub fn nearest(&self, point: [f32; 3], count: usize) -> Vec<&SpatialEntry<'a>> {
+        if count == 0 {
+            return vec![];
+        }
+
+        let point = Vec3::from(point);
+        let center = self.bucket(point);
+
+        let max_ring = self
+            .buckets
+            .keys()
+            .map(|&(x, y)| (x - center.0).abs().max((y - center.1).abs()))
+            .max()
+            .unwrap_or_default();
+
+        let mut found: Vec<(f32, &SpatialEntry<'a>)> = vec![];
+
+        for ring in 0..=max_ring {
+            for y in (center.1 - ring)..=(center.1 + ring) {
+                for x in (center.0 - ring)..=(center.0 + ring) {
+                    // only visit the outer edge of the ring
+                    if (x - center.0).abs() != ring && (y - center.1).abs() != ring {
+                        continue;
+                    }
+                    for &i in self.buckets.get(&(x, y)).into_iter().flatten() {
+                        let entry = &self.entries[i];
+                        found.push((entry.position().distance(point), entry));
+                    }
+                }
+            }
+
+            found.sort_by(|a, b| a.0.total_cmp(&b.0));
+            found.truncate(count);
+
+            // Anything outside of the visited rings is at least this far away.
+            #[allow(clippy::cast_precision_loss)]
+            let searched = ring as f32 * self.bucket_size;
+            if found.len() == count && found.last().is_some_and(|(distance, _)| *distance <= searched) {
+                break;
+            }
+        }
+
+        found.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin() -> Plugin {
+        let mut plugin = Plugin::new();
+        for (grid, positions) in [
+            ((0, 0), &[[100.0, 100.0, 0.0], [500.0, 100.0, 0.0], [8000.0, 8000.0, 0.0]][..]),
+            ((-1, 0), &[[-100.0, 100.0, 50.0]][..]),
+            ((5, 5), &[[40970.0, 40970.0, 0.0]][..]),
+        ] {
+            let mut cell = Cell {
+                data: CellData { grid, ..default() },
+                ..default()
+            };
+            for (refr_index, translation) in (1..).zip(positions) {
+                let reference = Reference {
+                    refr_index,
+                    id: format!("{}_{}_{refr_index}", grid.0, grid.1),
+                    translation: *translation,
+                    ..default()
+                };
+                cell.references.insert((0, refr_index), reference);
+            }
+            plugin.objects.push(cell.into());
+        }
+
+        // deleted and interior references are not indexed
+        let mut cell = Cell::default();
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        cell.references.insert((0, 1), Reference::default());
+        plugin.objects.push(cell.into());
+        if let Some(cell) = plugin.exterior_cell_mut((0, 0)) {
+            let reference = Reference {
+                refr_index: 9,
+                translation: [100.0, 100.0, 0.0],
+                deleted: Some(true),
+                ..default()
+            };
+            cell.references.insert((0, 9), reference);
+        }
+
+        plugin
+    }
+
+    fn ids<'a>(entries: impl IntoIterator<Item = &'a SpatialEntry<'a>>) -> Vec<&'a str> {
+        let mut ids: Vec<_> = entries.into_iter().map(|entry| entry.reference.id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn exterior_grid() {
+        assert_eq!(exterior_grid_of([0.0, 8191.0, 0.0]), (0, 0));
+        assert_eq!(exterior_grid_of([8192.0, -1.0, 0.0]), (1, -1));
+        assert_eq!(exterior_grid_of([-8193.0, 0.0, 0.0]), (-2, 0));
+    }
+
+    #[test]
+    fn queries() {
+        let plugin = plugin();
+        let index = SpatialIndex::new([&plugin]);
+        assert_eq!(index.len(), 5);
+
+        // across the border of two cells
+        assert_eq!(ids(index.within_radius([0.0, 100.0, 0.0], 200.0)), ["-1_0_1", "0_0_1"]);
+        assert_eq!(ids(index.within_radius([0.0, 100.0, 0.0], 1.0)), Vec::<&str>::new());
+
+        assert_eq!(
+            ids(index.within_box([0.0, 0.0, -10.0], [8000.0, 8000.0, 10.0])),
+            ["0_0_1", "0_0_2", "0_0_3"]
+        );
+        assert_eq!(ids(index.within_box([-200.0, 0.0, 0.0], [200.0, 200.0, 10.0])), ["0_0_1"]);
+
+        let nearest: Vec<_> = index
+            .nearest([450.0, 100.0, 0.0], 3)
+            .iter()
+            .map(|entry| entry.reference.id.as_str())
+            .collect();
+        assert_eq!(nearest, ["0_0_2", "0_0_1", "-1_0_1"]);
+
+        // the only candidate is many cells away
+        let nearest = index.nearest([5.0 * CELL_SIZE, 5.0 * CELL_SIZE, 0.0], 1);
+        assert_eq!(nearest[0].reference.id, "5_5_1");
+        assert_eq!(index.nearest([0.0; 3], 10).len(), 5);
+        assert!(index.nearest([0.0; 3], 0).is_empty());
+
+        // smaller buckets give the same results
+        let index = SpatialIndex::with_bucket_size([&plugin], 256.0);
+        assert_eq!(ids(index.within_radius([0.0, 100.0, 0.0], 200.0)), ["-1_0_1", "0_0_1"]);
+        let nearest: Vec<_> = index
+            .nearest([450.0, 100.0, 0.0], 3)
+            .iter()
+            .map(|entry| entry.reference.id.as_str())
+            .collect();
+        assert_eq!(nearest, ["0_0_2", "0_0_1", "-1_0_1"]);
+    }
+}
@@ -0,0 +1,388 @@
+// rust std imports
+use std::io::Write;
+
+// external imports
+use glam::{Vec3, Vec4};
+
+// internal imports
+use crate::prelude::*;
+
+/// Exports exterior terrain from `Landscape` records as a single mesh.
+#[derive(Clone, Debug, SmartDefault)]
+pub struct TerrainExporter {
+    /// Only export cells whose region matches this id (case-insensitive).
+    pub region: Option<String>,
+    /// Only export cells within these inclusive grid bounds.
+    pub bounds: Option<((i32, i32), (i32, i32))>,
+    /// Keep only every n-th vertex along each axis. Must be a power of two no greater than 64.
+    #[default(1)]
+    pub step: usize,
+}
+
+/// Terrain geometry produced by [`TerrainExporter`].
+///
+/// Positions are in world space with Z pointing up. Triangles are stored in pairs, one pair per grid quad.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TerrainMesh {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub colors: Vec<Vec4>,
+    /// The `VTEX` value of the texture tile each vertex belongs to. Zero is the default texture,
+    /// otherwise this is one greater than the `LandscapeTexture::index`.
+    pub texture_indices: Vec<u16>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl TerrainExporter {
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Build a mesh from the exterior terrain defined by `plugins`.
+    ///
+    /// Plugins should be given in load order, records from later plugins override earlier ones.
+    pub fn export<'a>(&self, plugins: impl IntoIterator<Item = &'a Plugin>) -> io::Result<TerrainMesh> {
+        if !self.step.is_power_of_two() || self.step > 64 {
+            return Reader::error(format!("Invalid terrain step: {}", self.step));
+        }
+
+        let mut landscapes: HashMap<_, &'a Landscape> = HashMap::new();
+        let mut regions: HashMap<_, Option<&'a str>> = HashMap::new();
+
+        for plugin in plugins {
+            for object in &plugin.objects {
+                match object {
+                    TES3Object::Landscape(landscape) => {
+                        landscapes.insert(landscape.grid, landscape);
+                    }
+                    TES3Object::Cell(cell) if cell.is_exterior() => {
+                        regions.insert(cell.data.grid, cell.region.as_deref());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut grids: Vec<_> = landscapes
+            .iter()
+            .filter(|(_, landscape)| {
+                !landscape.deleted()
+                    && landscape
+                        .landscape_flags
+                        .contains(LandscapeFlags::USES_VERTEX_HEIGHTS_AND_NORMALS)
+            })
+            .map(|(grid, _)| *grid)
+            .filter(|grid| self.includes(*grid, regions.get(grid).copied().flatten()))
+            .collect();
+        grids.sort_unstable();
+
+        if grids.is_empty() {
+            return Reader::error("No terrain to export");
+        }
+
+        let mut mesh = TerrainMesh::default();
+        for grid in grids {
+            mesh.append(landscapes[&grid], self.step)?;
+        }
+
+        Ok(mesh)
+    }
+
+    fn includes(&self, grid: (i32, i32), region: Option<&str>) -> bool {
+        if let Some(((min_x, min_y), (max_x, max_y))) = self.bounds {
+            if !(min_x..=max_x).contains(&grid.0) || !(min_y..=max_y).contains(&grid.1) {
+                return false;
+            }
+        }
+        match (&self.region, region) {
+            (Some(expected), Some(region)) => expected.eq_ignore_ascii_case(region),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+impl TerrainMesh {
+    fn append(&mut self, landscape: &Landscape, step: usize) -> io::Result<()> {
+        let vertices = landscape.calculate_world_vertices();
+        let normals = landscape.decode_vertex_normals();
+        let textures = landscape.decode_texture_indices();
+        let colors = if landscape.landscape_flags.contains(LandscapeFlags::USES_VERTEX_COLORS) {
+            landscape.decode_vertex_colors()
+        } else {
+            vec![Vec4::ONE; 65 * 65]
+        };
+
+        let Ok(base) = u32::try_from(self.positions.len()) else {
+            return Reader::error("Too many terrain vertices");
+        };
+
+        for y in (0..65).step_by(step) {
+            for x in (0..65).step_by(step) {
+                let i = y * 65 + x;
+                self.positions.push(vertices[i]);
+                self.normals.push(normals[i]);
+                self.colors.push(colors[i]);
+                self.texture_indices.push(textures[(y / 4).min(15)][(x / 4).min(15)]);
+            }
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let quads = (64 / step) as u32;
+
+        for qy in 0..quads {
+            for qx in 0..quads {
+                let a = base + qy * (quads + 1) + qx;
+                let b = a + quads + 1;
+                let c = a + 1;
+                let d = b + 1;
+                // Alternate the diagonal, matching `Landscape::calcuate_triangles`.
+                if (qx ^ qy) & 1 == 1 {
+                    self.triangles.push([a, c, b]);
+                    self.triangles.push([b, c, d]);
+                } else {
+                    self.triangles.push([d, b, a]);
+                    self.triangles.push([c, d, a]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The texture index of each grid quad, in the same order as the pairs of `triangles`.
+    fn quad_texture_indices(&self) -> impl Iterator<Item = u16> + '_ {
+        self.triangles.chunks_exact(2).map(|pair| {
+            let corner = pair.as_flattened().iter().min().copied().unwrap_or_default();
+            self.texture_indices[corner as usize]
+        })
+    }
+
+    /// Write the mesh as a Wavefront OBJ.
+    ///
+    /// Vertex colors are written after each position, and faces are grouped into `ltex_<index>` materials.
+    pub fn save_obj(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "# tes3 terrain export")?;
+
+        for (position, color) in self.positions.iter().zip(&self.colors) {
+            let Vec3 { x, y, z } = *position;
+            let [r, g, b, _] = color.to_array();
+            writeln!(writer, "v {x} {y} {z} {r} {g} {b}")?;
+        }
+
+        for normal in &self.normals {
+            let Vec3 { x, y, z } = *normal;
+            writeln!(writer, "vn {x} {y} {z}")?;
+        }
+
+        let mut material = None;
+        for (pair, texture_index) in self.triangles.chunks_exact(2).zip(self.quad_texture_indices()) {
+            if material != Some(texture_index) {
+                material = Some(texture_index);
+                writeln!(writer, "usemtl ltex_{texture_index}")?;
+            }
+            for [a, b, c] in pair.iter().map(|t| t.map(|i| i + 1)) {
+                writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the mesh as a binary glTF (GLB).
+    ///
+    /// Positions and normals are converted to glTF's Y-up convention. Texture indices are stored
+    /// in the application-specific `_TEXTURE_INDEX` attribute.
+    pub fn save_glb(&self, mut writer: impl Write) -> io::Result<()> {
+        let Ok(count) = u32::try_from(self.positions.len()) else {
+            return Reader::error("Too many terrain vertices");
+        };
+
+        let to_y_up = |v: &Vec3| Vec3::new(v.x, v.z, -v.y);
+
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+
+        let mut buffer = vec![];
+        let mut views = vec![];
+
+        // POSITION
+        let start = buffer.len();
+        for position in self.positions.iter().map(to_y_up) {
+            min = min.min(position);
+            max = max.max(position);
+            buffer.extend(position.to_array().iter().flat_map(|v| v.to_le_bytes()));
+        }
+        views.push((start, buffer.len(), 34962));
+
+        // NORMAL
+        let start = buffer.len();
+        for normal in self.normals.iter().map(to_y_up) {
+            buffer.extend(normal.to_array().iter().flat_map(|v| v.to_le_bytes()));
+        }
+        views.push((start, buffer.len(), 34962));
+
+        // COLOR_0
+        let start = buffer.len();
+        for color in &self.colors {
+            buffer.extend(color.to_array().iter().flat_map(|v| v.to_le_bytes()));
+        }
+        views.push((start, buffer.len(), 34962));
+
+        // _TEXTURE_INDEX
+        let start = buffer.len();
+        for &texture_index in &self.texture_indices {
+            buffer.extend(f32::from(texture_index).to_le_bytes());
+        }
+        views.push((start, buffer.len(), 34962));
+
+        // indices
+        let start = buffer.len();
+        for index in self.triangles.as_flattened() {
+            buffer.extend(index.to_le_bytes());
+        }
+        views.push((start, buffer.len(), 34963));
+
+        let buffer_views = views
+            .iter()
+            .map(|(start, end, target)| {
+                format!(
+                    r#"{{"buffer":0,"byteOffset":{start},"byteLength":{},"target":{target}}}"#,
+                    end - start
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let num_indices = self.triangles.len() * 3;
+        let json = format!(
+            concat!(
+                r#"{{"asset":{{"version":"2.0","generator":"tes3"}},"scene":0,"scenes":[{{"nodes":[0]}}],"#,
+                r#""nodes":[{{"mesh":0}}],"meshes":[{{"primitives":[{{"attributes":{{"#,
+                r#""POSITION":0,"NORMAL":1,"COLOR_0":2,"_TEXTURE_INDEX":3}},"indices":4}}]}}],"#,
+                r#""buffers":[{{"byteLength":{}}}],"bufferViews":[{}],"accessors":["#,
+                r#"{{"bufferView":0,"componentType":5126,"count":{count},"type":"VEC3","#,
+                r#""min":[{},{},{}],"max":[{},{},{}]}},"#,
+                r#"{{"bufferView":1,"componentType":5126,"count":{count},"type":"VEC3"}},"#,
+                r#"{{"bufferView":2,"componentType":5126,"count":{count},"type":"VEC4"}},"#,
+                r#"{{"bufferView":3,"componentType":5126,"count":{count},"type":"SCALAR"}},"#,
+                r#"{{"bufferView":4,"componentType":5125,"count":{num_indices},"type":"SCALAR"}}]}}"#,
+            ),
+            buffer.len(),
+            buffer_views,
+            min.x,
+            min.y,
+            min.z,
+            max.x,
+            max.y,
+            max.z,
+            count = count,
+            num_indices = num_indices,
+        );
+
+        let mut json = json.into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+        let Ok(total_size) = u32::try_from(12 + 8 + json.len() + 8 + buffer.len()) else {
+            return Reader::error("Terrain mesh is too large for glTF");
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            writer.write_all(b"glTF")?;
+            writer.write_all(&2u32.to_le_bytes())?;
+            writer.write_all(&total_size.to_le_bytes())?;
+            // JSON
+            writer.write_all(&(json.len() as u32).to_le_bytes())?;
+            writer.write_all(b"JSON")?;
+            writer.write_all(&json)?;
+            // BIN
+            writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+            writer.write_all(b"BIN\0")?;
+            writer.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin() -> Plugin {
+        let mut plugin = Plugin::new();
+        for (grid, region) in [((0, 0), "Bitter Coast Region"), ((1, 0), "West Gash Region")] {
+            let mut landscape = Landscape {
+                grid,
+                landscape_flags: LandscapeFlags::USES_VERTEX_HEIGHTS_AND_NORMALS,
+                ..default()
+            };
+            landscape
+                .vertex_normals
+                .data
+                .iter_mut()
+                .flatten()
+                .for_each(|normal| *normal = [0, 0, 127]);
+            landscape.texture_indices.data[0][0] = 3;
+
+            let mut cell = Cell {
+                region: Some(region.into()),
+                ..default()
+            };
+            cell.data.grid = grid;
+
+            plugin.objects.extend([cell.into(), landscape.into()]);
+        }
+        plugin
+    }
+
+    #[test]
+    fn export() -> io::Result<()> {
+        let plugin = plugin();
+
+        let mut exporter = TerrainExporter {
+            region: Some("bitter coast region".into()),
+            ..default()
+        };
+        let mesh = exporter.export([&plugin])?;
+        assert_eq!(mesh.positions.len(), 65 * 65);
+        assert_eq!(mesh.normals.len(), 65 * 65);
+        assert_eq!(mesh.triangles.len(), 64 * 64 * 2);
+        assert_eq!(mesh.positions[65 * 65 - 1], Vec3::new(8192.0, 8192.0, 0.0));
+        assert_eq!(mesh.texture_indices[..5], [3, 3, 3, 3, 0]);
+        assert!(mesh.triangles.as_flattened().iter().all(|&index| index < 65 * 65));
+
+        exporter.step = 4;
+        let mesh = exporter.export([&plugin])?;
+        assert_eq!(mesh.positions.len(), 17 * 17);
+        assert_eq!(mesh.triangles.len(), 16 * 16 * 2);
+
+        let mut obj = vec![];
+        mesh.save_obj(&mut obj)?;
+        let obj = String::from_utf8(obj).unwrap();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 17 * 17);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("f ")).count(), 16 * 16 * 2);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("usemtl")).count(), 2);
+
+        let mut glb = vec![];
+        mesh.save_glb(&mut glb)?;
+        assert_eq!(&glb[..4], b"glTF");
+        assert_eq!(glb[8..12], u32::try_from(glb.len()).unwrap().to_le_bytes());
+
+        // both cells, indexed after one another
+        let mesh = TerrainExporter::new().export([&plugin])?;
+        assert_eq!(mesh.positions.len(), 2 * 65 * 65);
+        assert_eq!(mesh.triangles.len(), 2 * 64 * 64 * 2);
+        assert_eq!(mesh.triangles.as_flattened().iter().max(), Some(&(2 * 65 * 65 - 1)));
+
+        exporter.step = 3;
+        assert!(exporter.export([&plugin]).is_err());
+        exporter.step = 1;
+        exporter.bounds = Some(((5, 5), (6, 6)));
+        assert!(exporter.export([&plugin]).is_err());
+
+        Ok(())
+    }
+}
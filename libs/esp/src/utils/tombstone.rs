@@ -0,0 +1,505 @@
+// internal imports
+use crate::prelude::*;
+
+impl Plugin {
+    /// Delete the record with the given tag and id from `master`, one of this plugin's masters.
+    ///
+    /// If this plugin already overrides the record, the override is flagged as deleted, keeping
+    /// its data so that the deletion can be undone. Otherwise a minimal tombstone is added, which
+    /// holds only the id and the data the record type requires, such as the value type of a game
+    /// setting or the type of a dialogue topic.
+    ///
+    /// Cells are identified by their [`EditorId`], and their tombstones keep only the name, region
+    /// and grid. A dialogue info tombstone is placed after its topic, adding an override of the
+    /// topic first if this plugin does not have one, as the game finds infos by the topic before
+    /// them.
+    ///
+    /// Fails if `master` has no such record, or for record types that are not identified by id,
+    /// such as skills and landscapes.
+    pub fn delete_master_record(&mut self, master: &Self, tag: [u8; 4], id: &str) -> io::Result<()> {
+        let Some(position) = master.find_record(tag, id) else {
+            return Reader::error(format!(
+                "No {} record with id {id} in the master",
+                String::from_utf8_lossy(&tag)
+            ));
+        };
+        let record = &master.objects[position];
+        let Some(mut tombstone) = tombstone(record) else {
+            return Reader::error(format!("{} records cannot be deleted by id", record.tag_str()));
+        };
+
+        if let Some(position) = self.find_override(record) {
+            self.objects[position].set_deleted(true);
+            return Ok(());
+        }
+        tombstone.set_deleted(true);
+        self.tombstones.push(tombstone.clone());
+
+        let TES3Object::DialogueInfo(_) = record else {
+            self.insert_or_replace(tombstone);
+            return Ok(());
+        };
+        let Some(TES3Object::Dialogue(dialogue)) = master.objects[..position]
+            .iter()
+            .rfind(|object| matches!(object, TES3Object::Dialogue(_)))
+        else {
+            return Reader::error(format!("Dialogue info {id} does not follow a dialogue topic"));
+        };
+        let position = if let Some(topic) = self.find_record(*Dialogue::TAG, &dialogue.id) {
+            self.objects[topic + 1..]
+                .iter()
+                .position(|object| !matches!(object, TES3Object::DialogueInfo(_)))
+                .map_or(self.objects.len(), |end| topic + 1 + end)
+        } else {
+            self.objects.push(
+                Dialogue {
+                    id: dialogue.id.clone(),
+                    dialogue_type: dialogue.dialogue_type,
+                    ..default()
+                }
+                .into(),
+            );
+            self.objects.len()
+        };
+        self.objects.insert(position, tombstone);
+        self.invalidate_index();
+
+        Ok(())
+    }
+
+    /// Undo [`Plugin::delete_master_record`], removing the tombstone or clearing the deleted flag
+    /// of the override. Returns `false` if the record was not deleted.
+    ///
+    /// Only unchanged tombstones added by this plugin are removed. Any other deleted record,
+    /// including a tombstone loaded from a file, is kept with its deleted flag cleared. The
+    /// topic added for a dialogue info tombstone is kept.
+    pub fn undelete_master_record(&mut self, tag: [u8; 4], id: &str) -> bool {
+        let Some(position) = self.find_record(tag, id) else {
+            return false;
+        };
+        let object = &mut self.objects[position];
+        if !object.deleted() {
+            return false;
+        }
+
+        if let Some(tombstone) = self.tombstones.iter().position(|tombstone| tombstone == object) {
+            self.tombstones.swap_remove(tombstone);
+            self.objects.remove(position);
+        } else {
+            object.set_deleted(false);
+        }
+
+        true
+    }
+
+    /// Delete the reference `(mast_index, refr_index)` from one of this plugin's masters.
+    ///
+    /// `cell` is the cell containing the reference, as found in the master or in this plugin.
+    /// It provides the reference's object id, and the cell record to add if this plugin does not
+    /// already override the cell. If this plugin already overrides the reference, the override is
+    /// flagged as deleted, keeping its data so that the deletion can be undone.
+    pub fn delete_master_reference(&mut self, cell: &Cell, mast_index: u32, refr_index: u32) -> io::Result<()> {
+        if mast_index == 0 {
+            return Reader::error("References with a mast_index of 0 belong to this plugin, not a master");
+        }
+        let Some(reference) =
+            (cell.references.get(&(mast_index, refr_index))).or_else(|| cell.references.get(&(0, refr_index)))
+        else {
+            return Reader::error(format!(
+                "No reference with refr_index {refr_index} in cell {}",
+                cell.editor_id()
+            ));
+        };
+
+        let tombstone = Reference {
+            mast_index,
+            refr_index,
+            id: reference.id.clone(),
+            temporary: reference.temporary,
+            deleted: Some(true),
+            ..default()
+        };
+
+        if self.find_cell_mut(cell).is_none() {
+            let mut header = Cell {
+                references: default(),
+                ..cell.clone()
+            };
+            header.original_bytes.clear();
+            self.objects.push(header.into());
+        }
+        let Some(plugin_cell) = self.find_cell_mut(cell) else {
+            unreachable!("the cell was just added");
+        };
+
+        plugin_cell
+            .references
+            .entry((mast_index, refr_index))
+            .and_modify(|reference| reference.deleted = Some(true))
+            .or_insert(tombstone);
+
+        Ok(())
+    }
+
+    /// Undo [`Plugin::delete_master_reference`], removing the tombstone or clearing the deleted
+    /// flag of the override. The cell record is kept. Returns `false` if the reference was not
+    /// deleted.
+    pub fn undelete_master_reference(&mut self, cell: &Cell, mast_index: u32, refr_index: u32) -> bool {
+        let Some(plugin_cell) = self.find_cell_mut(cell) else {
+            return false;
+        };
+        let key = (mast_index, refr_index);
+        let Some(reference) = plugin_cell.references.get_mut(&key) else {
+            return false;
+        };
+        if !reference.deleted() {
+            return false;
+        }
+
+        let tombstone = Reference {
+            mast_index,
+            refr_index,
+            id: reference.id.clone(),
+            temporary: reference.temporary,
+            deleted: Some(true),
+            ..default()
+        };
+        if *reference == tombstone {
+            plugin_cell.references.remove(&key);
+        } else {
+            reference.deleted = None;
+        }
+
+        true
+    }
+
+    /// The position of this plugin's record for the same object as `record`, matching cells the
+    /// way [`Plugin::find_cell_mut`] does.
+    fn find_override(&self, record: &TES3Object) -> Option<usize> {
+        let TES3Object::Cell(cell) = record else {
+            return self.find_record(*record.tag(), &record.editor_id());
+        };
+        self.objects.iter().rposition(|object| match object {
+            TES3Object::Cell(other) => {
+                other.exterior_coords() == cell.exterior_coords()
+                    && (cell.is_exterior() || other.name.eq_ignore_ascii_case(&cell.name))
+            }
+            _ => false,
+        })
+    }
+
+    fn find_record(&self, tag: [u8; 4], id: &str) -> Option<usize> {
+        self.objects
+            .iter()
+            .rposition(|object| *object.tag() == tag && object.editor_id().eq_ignore_ascii_case(id))
+    }
+
+    /// This plugin's record for the same cell as `cell`, matching exteriors by grid and
+    /// interiors by name.
+    fn find_cell_mut(&mut self, cell: &Cell) -> Option<&mut Cell> {
+        match cell.exterior_coords() {
+            Some(grid) => self.exterior_cell_mut(grid),
+            None => self
+                .objects_of_type_mut::<Cell>()
+                .find(|other| other.is_interior() && other.name.eq_ignore_ascii_case(&cell.name)),
+        }
+    }
+}
+
+/// The smallest record that deletes `record`, or `None` if its type has no id.
+///
+/// Dialogue infos keep their links to the neighbouring infos and their data, which holds the
+/// dialogue type.
+fn tombstone(record: &TES3Object) -> Option<TES3Object> {
+    macro_rules! tombstone {
+        ($($T:ident)*) => {
+            match record {
+                TES3Object::GameSetting(setting) => GameSetting {
+                    id: setting.id.clone(),
+                    value: match setting.value {
+                        GameSettingValue::Float(_) => GameSettingValue::Float(0.0),
+                        GameSettingValue::Integer(_) => GameSettingValue::Integer(0),
+                        GameSettingValue::String(_) => GameSettingValue::String(default()),
+                    },
+                    ..default()
+                }
+                .into(),
+                TES3Object::GlobalVariable(global) => GlobalVariable {
+                    id: global.id.clone(),
+                    value: GlobalValue::from_f32(global.value.global_type(), 0.0),
+                    ..default()
+                }
+                .into(),
+                TES3Object::Cell(cell) => Cell {
+                    name: cell.name.clone(),
+                    region: cell.region.clone(),
+                    data: cell.data.clone(),
+                    ..default()
+                }
+                .into(),
+                TES3Object::DialogueInfo(info) => DialogueInfo {
+                    id: info.id.clone(),
+                    prev_id: info.prev_id.clone(),
+                    next_id: info.next_id.clone(),
+                    data: info.data.clone(),
+                    ..default()
+                }
+                .into(),
+                TES3Object::Dialogue(dialogue) => Dialogue {
+                    id: dialogue.id.clone(),
+                    dialogue_type: dialogue.dialogue_type,
+                    ..default()
+                }
+                .into(),
+                $(TES3Object::$T(record) => $T { id: record.id.clone(), ..default() }.into(),)*
+                _ => return None,
+            }
+        };
+    }
+    Some(tombstone!(
+        Activator Alchemy Apparatus Armor Birthsign Bodypart Book Class Clothing Container Creature
+        Door Enchanting Faction Ingredient LandscapeTexture LeveledCreature LeveledItem Light Lockpick
+        MiscItem Npc Probe Race Region RepairItem Script Sound SoundGen Spell StartScript Static Weapon
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delete_master_record() {
+        let master = Plugin {
+            objects: vec![
+                Npc {
+                    id: "fargoth".into(),
+                    name: "Fargoth".into(),
+                    ..default()
+                }
+                .into(),
+                Weapon {
+                    id: "Iron_Club".into(),
+                    ..default()
+                }
+                .into(),
+                Static {
+                    id: "ex_common_plat_01".into(),
+                    ..default()
+                }
+                .into(),
+                Skill::default().into(),
+            ],
+            ..default()
+        };
+
+        let mut plugin = Plugin::new();
+        plugin.objects.push(
+            Weapon {
+                id: "iron_club".into(),
+                name: "Iron Club".into(),
+                ..default()
+            }
+            .into(),
+        );
+        // an override that happens to hold no data is not a tombstone
+        plugin.objects.push(
+            Static {
+                id: "ex_common_plat_01".into(),
+                ..default()
+            }
+            .into(),
+        );
+
+        plugin.delete_master_record(&master, *Npc::TAG, "fargoth").unwrap();
+        plugin.delete_master_record(&master, *Weapon::TAG, "iron_club").unwrap();
+        plugin
+            .delete_master_record(&master, *Static::TAG, "ex_common_plat_01")
+            .unwrap();
+        assert!(plugin.delete_master_record(&master, *Npc::TAG, "vodunius").is_err());
+        let skill = Skill::default().editor_id().into_owned();
+        assert!(plugin.delete_master_record(&master, *Skill::TAG, &skill).is_err());
+
+        let npc = plugin.get::<Npc>("fargoth").unwrap();
+        assert!(npc.deleted());
+        assert_eq!(npc.name, "");
+        let weapon = plugin.get::<Weapon>("iron_club").unwrap();
+        assert!(weapon.deleted());
+        assert_eq!(weapon.name, "Iron Club");
+
+        // tombstones must survive a round trip
+        let bytes = plugin.save_bytes().unwrap();
+        let mut loaded = Plugin::new();
+        loaded.load_bytes(&bytes).unwrap();
+        assert!(loaded.get::<Npc>("fargoth").unwrap().deleted());
+
+        assert!(plugin.undelete_master_record(*Npc::TAG, "FARGOTH"));
+        assert!(plugin.undelete_master_record(*Weapon::TAG, "iron_club"));
+        assert!(!plugin.undelete_master_record(*Weapon::TAG, "iron_club"));
+        assert!(plugin.undelete_master_record(*Static::TAG, "ex_common_plat_01"));
+        assert!(plugin.get::<Npc>("fargoth").is_none());
+        assert!(!plugin.get::<Weapon>("iron_club").unwrap().deleted());
+        assert!(!plugin.get::<Static>("ex_common_plat_01").unwrap().deleted());
+
+        // loading replaces the objects, so the tombstones added before are forgotten
+        plugin.delete_master_record(&master, *Npc::TAG, "fargoth").unwrap();
+        plugin.load_bytes(&bytes).unwrap();
+        assert!(plugin.tombstones.is_empty());
+    }
+
+    #[test]
+    fn delete_typed_master_records() {
+        let master = Plugin {
+            objects: vec![
+                GameSetting {
+                    id: "sWerewolfPopup".into(),
+                    value: GameSettingValue::String("Werewolf".into()),
+                    ..default()
+                }
+                .into(),
+                GameSetting {
+                    id: "iWereWolfBounty".into(),
+                    value: GameSettingValue::Integer(10000),
+                    ..default()
+                }
+                .into(),
+                Dialogue {
+                    id: "A1_1_FindSpymaster".into(),
+                    dialogue_type: DialogueType2::Journal,
+                    ..default()
+                }
+                .into(),
+            ],
+            ..default()
+        };
+
+        let mut plugin = Plugin::new();
+        plugin
+            .delete_master_record(&master, *GameSetting::TAG, "sWerewolfPopup")
+            .unwrap();
+        plugin
+            .delete_master_record(&master, *GameSetting::TAG, "iWereWolfBounty")
+            .unwrap();
+        plugin
+            .delete_master_record(&master, *Dialogue::TAG, "A1_1_FindSpymaster")
+            .unwrap();
+
+        let bytes = plugin.save_bytes().unwrap();
+        let mut loaded = Plugin::new();
+        loaded.load_bytes(&bytes).unwrap();
+
+        let setting = loaded.get::<GameSetting>("swerewolfpopup").unwrap();
+        assert_eq!(setting.value, GameSettingValue::String(String::new()));
+        let setting = loaded.get::<GameSetting>("iwerewolfbounty").unwrap();
+        assert_eq!(setting.value, GameSettingValue::Integer(0));
+        let dialogue = loaded.get::<Dialogue>("a1_1_findspymaster").unwrap();
+        assert_eq!(dialogue.dialogue_type, DialogueType2::Journal);
+        assert!(dialogue.deleted());
+
+        assert!(plugin.undelete_master_record(*Dialogue::TAG, "a1_1_findspymaster"));
+        assert!(plugin.get::<Dialogue>("a1_1_findspymaster").is_none());
+        // tombstones loaded from a file are not known to be tombstones
+        assert!(loaded.undelete_master_record(*Dialogue::TAG, "a1_1_findspymaster"));
+        assert!(!loaded.get::<Dialogue>("a1_1_findspymaster").unwrap().deleted());
+    }
+
+    #[test]
+    fn delete_master_cells_and_infos() {
+        let info = |id: &str, prev_id: &str, next_id: &str| -> TES3Object {
+            DialogueInfo {
+                id: id.into(),
+                prev_id: prev_id.into(),
+                next_id: next_id.into(),
+                text: "Some text".into(),
+                ..default()
+            }
+            .into()
+        };
+        let mut cell = Cell {
+            name: "Seyda Neen, Census and Excise Office".into(),
+            water_height: Some(0.0),
+            ..default()
+        };
+        cell.data.flags |= CellFlags::IS_INTERIOR;
+        let master = Plugin {
+            objects: vec![
+                Dialogue {
+                    id: "Background".into(),
+                    ..default()
+                }
+                .into(),
+                info("1", "", "2"),
+                info("2", "1", ""),
+                cell.into(),
+            ],
+            ..default()
+        };
+
+        let mut plugin = Plugin::new();
+        plugin.delete_master_record(&master, *DialogueInfo::TAG, "2").unwrap();
+        plugin.delete_master_record(&master, *DialogueInfo::TAG, "1").unwrap();
+        plugin
+            .delete_master_record(&master, *Cell::TAG, "Seyda Neen, Census and Excise Office")
+            .unwrap();
+
+        let bytes = plugin.save_bytes().unwrap();
+        let mut loaded = Plugin::new();
+        loaded.load_bytes(&bytes).unwrap();
+
+        // the topic comes first, and is not deleted
+        let TES3Object::Dialogue(dialogue) = &loaded.objects[0] else {
+            panic!("expected the topic first, found {}", loaded.objects[0].tag_str());
+        };
+        assert_eq!(dialogue.id, "Background");
+        assert!(!dialogue.deleted());
+        let infos: Vec<_> = loaded.objects_of_type::<DialogueInfo>().collect();
+        assert_eq!(infos.len(), 2);
+        assert!(infos.iter().all(|info| info.deleted() && info.text.is_empty()));
+        assert_eq!((infos[0].id.as_str(), infos[0].prev_id.as_str()), ("2", "1"));
+        assert!(matches!(loaded.objects[2], TES3Object::DialogueInfo(_)));
+        let cell = loaded.objects_of_type::<Cell>().next().unwrap();
+        assert!(cell.deleted());
+        assert!(cell.is_interior());
+        assert_eq!(cell.water_height, None);
+
+        assert!(plugin.undelete_master_record(*DialogueInfo::TAG, "1"));
+        assert!(plugin.undelete_master_record(*Cell::TAG, "seyda neen, census and excise office"));
+        assert_eq!(plugin.objects_of_type::<DialogueInfo>().count(), 1);
+        assert_eq!(plugin.objects_of_type::<Dialogue>().count(), 1);
+        assert_eq!(plugin.objects_of_type::<Cell>().count(), 0);
+    }
+
+    #[test]
+    fn delete_master_reference() {
+        let mut master_cell = Cell {
+            name: "Seyda Neen, Arrille's Tradehouse".into(),
+            atmosphere_data: Some(default()),
+            ..default()
+        };
+        master_cell.data.flags |= CellFlags::IS_INTERIOR;
+        master_cell.references.insert(
+            (0, 7),
+            Reference {
+                refr_index: 7,
+                id: "arrille".into(),
+                translation: [1.0, 2.0, 3.0],
+                ..default()
+            },
+        );
+
+        let mut plugin = Plugin::new();
+        plugin.delete_master_reference(&master_cell, 1, 7).unwrap();
+        assert!(plugin.delete_master_reference(&master_cell, 1, 8).is_err());
+        assert!(plugin.delete_master_reference(&master_cell, 0, 7).is_err());
+
+        let cell = plugin.objects_of_type::<Cell>().next().unwrap();
+        assert_eq!(cell.name, master_cell.name);
+        assert_eq!(cell.atmosphere_data, master_cell.atmosphere_data);
+        let reference = &cell.references[&(1, 7)];
+        assert_eq!(reference.id, "arrille");
+        assert!(reference.deleted());
+
+        assert!(plugin.undelete_master_reference(&master_cell, 1, 7));
+        assert!(!plugin.undelete_master_reference(&master_cell, 1, 7));
+        assert!(plugin.objects_of_type::<Cell>().next().unwrap().references.is_empty());
+    }
+}
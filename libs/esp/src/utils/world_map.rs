@@ -0,0 +1,237 @@
+// rust std imports
+#[cfg(feature = "png")]
+use std::path::Path;
+
+// internal imports
+use crate::prelude::*;
+
+/// The number of pixels spanned by a single cell, one per `WorldMapData` sample.
+pub const WORLD_MAP_CELL_SIZE: usize = 9;
+
+/// An RGBA image of the exterior world, as produced by [`WorldMapRenderer`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WorldMapImage {
+    pub width: usize,
+    pub height: usize,
+    /// The grid coordinates of the cell in the top-left corner of the image.
+    pub origin: (i32, i32),
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// Renders the exterior world map from `Landscape::world_map_data`, `Cell::map_color` and `Region::map_color`.
+///
+/// Cell map colors take priority over region map colors. When neither is present only the terrain shading is used.
+#[derive(Clone, Debug, SmartDefault)]
+pub struct WorldMapRenderer {
+    /// How strongly cell and region map colors are blended over the terrain shading.
+    #[default(0.5)]
+    pub tint_strength: f32,
+    /// The color used for cells without any landscape data.
+    #[default([38, 56, 51, 255])]
+    pub water_color: [u8; 4],
+}
+
+#[derive(Default)]
+struct MapCell<'a> {
+    world_map_data: Option<&'a WorldMapData>,
+    map_color: Option<[u8; 4]>,
+    region: Option<&'a str>,
+}
+
+impl WorldMapRenderer {
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Render the exterior world defined by `plugins`.
+    ///
+    /// Plugins should be given in load order, records from later plugins override earlier ones.
+    #[allow(clippy::cast_sign_loss)]
+    pub fn render<'a>(&self, plugins: impl IntoIterator<Item = &'a Plugin>) -> WorldMapImage {
+        let mut cells: HashMap<(i32, i32), MapCell<'a>> = HashMap::new();
+        let mut regions: HashMap<String, [u8; 4]> = HashMap::new();
+
+        for plugin in plugins {
+            for object in &plugin.objects {
+                match object {
+                    TES3Object::Cell(cell) if cell.is_exterior() => {
+                        let entry = cells.entry(cell.data.grid).or_default();
+                        if cell.map_color.is_some() {
+                            entry.map_color = cell.map_color;
+                        }
+                        if let Some(region) = &cell.region {
+                            entry.region = Some(region);
+                        }
+                    }
+                    TES3Object::Landscape(landscape) if landscape.landscape_flags.uses_world_map_data() => {
+                        cells.entry(landscape.grid).or_default().world_map_data = Some(&landscape.world_map_data);
+                    }
+                    TES3Object::Region(region) => {
+                        regions.insert(region.id.to_ascii_lowercase(), region.map_color);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let Some(((min_x, min_y), (max_x, max_y))) = grid_bounds(cells.keys().copied()) else {
+            return WorldMapImage::default();
+        };
+
+        let columns = (max_x - min_x + 1) as usize;
+        let rows = (max_y - min_y + 1) as usize;
+
+        let width = columns * WORLD_MAP_CELL_SIZE;
+        let height = rows * WORLD_MAP_CELL_SIZE;
+        let mut pixels = vec![self.water_color; width * height];
+
+        for (&(grid_x, grid_y), cell) in &cells {
+            let Some(world_map_data) = cell.world_map_data else {
+                continue;
+            };
+
+            let tint = cell.map_color.or_else(|| {
+                let region = cell.region?.to_ascii_lowercase();
+                regions.get(&region).copied()
+            });
+
+            // Images are stored top-down, with north facing up.
+            let column = (grid_x - min_x) as usize;
+            let row = (max_y - grid_y) as usize;
+
+            for (y, samples) in world_map_data.data.iter().enumerate() {
+                for (x, &sample) in samples.iter().enumerate() {
+                    let px = column * WORLD_MAP_CELL_SIZE + x;
+                    let py = row * WORLD_MAP_CELL_SIZE + (WORLD_MAP_CELL_SIZE - 1 - y);
+                    pixels[py * width + px] = self.shade(sample, tint);
+                }
+            }
+        }
+
+        WorldMapImage {
+            width,
+            height,
+            origin: (min_x, max_y),
+            pixels,
+        }
+    }
+
+    /// Color a single `WorldMapData` sample, using the same color ramp as the game's world map.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn shade(&self, sample: i8, tint: Option<[u8; 4]>) -> [u8; 4] {
+        let mut height = f32::from(sample) / 128.0;
+
+        let (base, slope): ([f32; 3], [f32; 3]) = if height < 0.0 {
+            ([38.0, 56.0, 51.0], [14.0, 20.0, 18.0])
+        } else if height < 0.3 {
+            height = if height < 0.1 { height * 8.0 } else { height + 0.7 };
+            ([66.0, 48.0, 33.0], [-32.0, -23.0, -16.0])
+        } else {
+            height = (height - 0.3) * 1.428;
+            ([34.0, 25.0, 17.0], [-29.0, -20.0, -12.0])
+        };
+
+        let mut color = [0.0f32; 3];
+        for ((c, base), slope) in color.iter_mut().zip(base).zip(slope) {
+            *c = slope.mul_add(height, base).clamp(0.0, 255.0);
+        }
+
+        if let (Some(tint), true) = (tint, sample >= 0) {
+            let t = self.tint_strength.clamp(0.0, 1.0);
+            for (c, tint) in color.iter_mut().zip(tint) {
+                *c = c.mul_add(1.0 - t, f32::from(tint) * t);
+            }
+        }
+
+        let [r, g, b] = color.map(|c| c.round() as u8);
+        [r, g, b, 255]
+    }
+}
+
+impl WorldMapImage {
+    /// The raw RGBA bytes of the image, in row-major order.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.pixels.as_flattened()
+    }
+
+    #[cfg(feature = "png")]
+    pub fn save_png_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![];
+        {
+            let width = u32::try_from(self.width).map_err(io::Error::other)?;
+            let height = u32::try_from(self.height).map_err(io::Error::other)?;
+
+            let mut encoder = png::Encoder::new(&mut bytes, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer = encoder.write_header().map_err(io::Error::other)?;
+            writer.write_image_data(self.as_bytes()).map_err(io::Error::other)?;
+        }
+        Ok(bytes)
+    }
+
+    #[cfg(feature = "png")]
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.save_png_bytes()?)
+    }
+}
+
+fn grid_bounds(grids: impl IntoIterator<Item = (i32, i32)>) -> Option<((i32, i32), (i32, i32))> {
+    grids.into_iter().fold(None, |bounds, (x, y)| {
+        let ((min_x, min_y), (max_x, max_y)) = bounds.unwrap_or(((x, y), (x, y)));
+        Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let mut landscape = Landscape {
+            grid: (2, -1),
+            ..default()
+        };
+        landscape.vertex_heights.offset = 192.0;
+        landscape.update_world_map_data();
+
+        let cell = |grid, region: Option<&str>| {
+            let mut cell = Cell {
+                region: region.map(String::from),
+                ..default()
+            };
+            cell.data.grid = grid;
+            cell
+        };
+        let region = Region {
+            id: "Ascadian Isles Region".into(),
+            map_color: [255, 0, 0, 255],
+            ..default()
+        };
+
+        let mut plugin = Plugin::new();
+        plugin.objects.extend([
+            cell((2, -1), Some("ascadian isles region")).into(),
+            cell((1, 0), None).into(),
+            landscape.into(),
+            region.into(),
+        ]);
+
+        let renderer = WorldMapRenderer::new();
+        let image = renderer.render([&plugin]);
+        assert_eq!(
+            (image.width, image.height),
+            (2 * WORLD_MAP_CELL_SIZE, 2 * WORLD_MAP_CELL_SIZE)
+        );
+        assert_eq!(image.origin, (1, 0));
+        assert_eq!(image.as_bytes().len(), image.width * image.height * 4);
+
+        // the top-left cell has no landscape, the bottom-right one is tinted by its region
+        assert_eq!(image.pixels[0], renderer.water_color);
+        let pixel = image.pixels[image.pixels.len() - 1];
+        assert_eq!(pixel, renderer.shade(12, Some([255, 0, 0, 255])));
+        assert!(pixel[0] > pixel[1]);
+    }
+}
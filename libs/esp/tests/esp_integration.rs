@@ -1,6 +1,6 @@
 use tempfile::{NamedTempFile, TempDir};
 
-use esp::Plugin;
+use esp::{LoadOptions, Plugin};
 
 fn create_temp_file() -> (TempDir, NamedTempFile) {
     let dir = TempDir::new().unwrap();
@@ -26,6 +26,28 @@ fn load_save() -> std::io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn load_save_original_bytes() -> std::io::Result<()> {
+    let src_path = "tests/assets/all_types.esp";
+    let src_bytes = std::fs::read(src_path)?;
+
+    let options = LoadOptions {
+        keep_original_bytes: true,
+        ..LoadOptions::new()
+    };
+
+    let mut plugin = Plugin::new();
+    plugin.load_bytes_with_options(&src_bytes, &options)?;
+    assert_eq!(src_bytes, plugin.save_bytes()?);
+
+    // modified records are saved normally
+    let header = plugin.header_mut().unwrap();
+    header.author = String::from("Someone Else").into();
+    assert_ne!(src_bytes, plugin.save_bytes()?);
+
+    Ok(())
+}
+
 #[test]
 fn load_save_json() -> std::io::Result<()> {
     let src_path = "tests/assets/all_types.esp";
@@ -31,11 +31,19 @@ pub fn derive_tes3object(input: TokenStream) -> TokenStream {
         _ => panic!("derive(TES3Object) must be on the TES3Object enum"),
     };
 
-    let idents = parse_variant_idents(variants);
-    let tags = parse_variant_tags(variants);
+    // Variants without a `#[tag(..)]` attribute hold records with unrecognized tags.
+    let (tagged, untagged): (Vec<_>, Vec<_>) = variants.iter().partition(|v| parse_variant_tag(v).is_some());
+    assert!(
+        untagged.len() <= 1,
+        "derive(TES3Object) supports at most one untagged variant"
+    );
 
-    let impl_variants = tes3object_variant_impls(&idents, &tags);
-    let impl_object = tes3object_inherent_impls(&idents);
+    let idents = parse_variant_idents(tagged.iter().copied());
+    let tags: Vec<_> = tagged.iter().filter_map(|v| parse_variant_tag(v)).collect();
+    let unknown = parse_variant_idents(untagged.iter().copied()).pop();
+
+    let impl_variants = tes3object_variant_impls(&idents, &tags, unknown.as_ref());
+    let impl_object = tes3object_inherent_impls(&idents, unknown.as_ref());
 
     let output = quote! {
         const _: () = {
@@ -47,7 +55,7 @@ pub fn derive_tes3object(input: TokenStream) -> TokenStream {
     output.into()
 }
 
-fn tes3object_variant_impls(idents: &[syn::Ident], tags: &[syn::LitStr]) -> impl ToTokens {
+fn tes3object_variant_impls(idents: &[syn::Ident], tags: &[syn::LitStr], unknown: Option<&syn::Ident>) -> impl ToTokens {
     let tags_bytes = tags //
         .iter()
         .map(|tag| syn::LitByteStr::new(tag.value().as_bytes(), tag.span()));
@@ -56,6 +64,8 @@ fn tes3object_variant_impls(idents: &[syn::Ident], tags: &[syn::LitStr]) -> impl
         .iter()
         .map(|ident| syn::LitStr::new(&ident.to_string(), ident.span()));
 
+    let all_idents: Vec<_> = idents.iter().chain(unknown).collect();
+
     quote! {
         #(
             #[doc(hidden)]
@@ -64,32 +74,34 @@ fn tes3object_variant_impls(idents: &[syn::Ident], tags: &[syn::LitStr]) -> impl
                 pub const TAG_STR: &'static str = #tags;
                 pub const TYPE_NAME: &'static str = #idents_str;
             }
+        )*
 
-            impl TryFrom<TES3Object> for #idents {
+        #(
+            impl TryFrom<TES3Object> for #all_idents {
                 type Error = ();
                 fn try_from(value: TES3Object) -> Result<Self, Self::Error> {
                     match value {
-                        TES3Object::#idents(inner) => Ok(inner),
+                        TES3Object::#all_idents(inner) => Ok(inner),
                         _ => Err(())
                     }
                 }
             }
 
-            impl<'a> TryFrom<&'a TES3Object> for &'a #idents {
+            impl<'a> TryFrom<&'a TES3Object> for &'a #all_idents {
                 type Error = ();
                 fn try_from(value: &'a TES3Object) -> Result<Self, Self::Error> {
                     match value {
-                        TES3Object::#idents(inner) => Ok(inner),
+                        TES3Object::#all_idents(inner) => Ok(inner),
                         _ => Err(())
                     }
                 }
             }
 
-            impl<'a> TryFrom<&'a mut TES3Object> for &'a mut #idents {
+            impl<'a> TryFrom<&'a mut TES3Object> for &'a mut #all_idents {
                 type Error = ();
                 fn try_from(value: &'a mut TES3Object) -> Result<Self, Self::Error> {
                     match value {
-                        TES3Object::#idents(inner) => Ok(inner),
+                        TES3Object::#all_idents(inner) => Ok(inner),
                         _ => Err(())
                     }
                 }
@@ -98,20 +110,42 @@ fn tes3object_variant_impls(idents: &[syn::Ident], tags: &[syn::LitStr]) -> impl
     }
 }
 
-fn tes3object_inherent_impls(idents: &[syn::Ident]) -> impl ToTokens {
+fn tes3object_inherent_impls(idents: &[syn::Ident], unknown: Option<&syn::Ident>) -> impl ToTokens {
+    // Unrecognized records are kept as raw bytes in lenient mode.
+    let load_unknown = unknown.map(|unknown| {
+        quote! {
+            _ if stream.lenient => {
+                let mut inner: #unknown = stream.load()?;
+                inner.tag = tag;
+                inner.unused = unused;
+                Ok(Self::#unknown(inner))
+            }
+        }
+    });
+
+    // Their tag and the unused header field are restored on save.
+    let save_unknown = unknown.map(|unknown| {
+        quote! {
+            TES3Object::#unknown(obj) => { stream.save(obj)?; unused = obj.unused; &obj.tag }
+        }
+    });
+
     quote! {
         use bytes_io::*;
 
         impl Load for TES3Object {
             fn load(stream: &mut Reader<'_>) -> io::Result<Self> {
                 let tag = stream.load()?;
-                stream.skip(8)?; // skip size/padding
+                stream.skip(4)?; // skip size
+                #[allow(unused_variables)]
+                let unused: u32 = stream.load()?;
 
                 match &tag {
                     #(
                         #idents::TAG => Ok(Self::#idents(stream.load()?)),
                     )*
-                    _ => Reader::error(format!("Unexpected Tag: {}", tag.to_str_lossy()))?,
+                    #load_unknown
+                    _ => stream.fail(LoadErrorKind::UnexpectedTag(tag))?,
                 }
             }
         }
@@ -124,20 +158,24 @@ fn tes3object_inherent_impls(idents: &[syn::Ident]) -> impl ToTokens {
                 stream.save(&[0u32; 3])?;
 
                 // save object & get tag
+                #[allow(unused_mut)]
+                let mut unused = 0u32;
                 let tag = match self {
                     #(
                         TES3Object::#idents(obj) => { stream.save(obj)?; obj.tag() }
                     )*
+                    #save_unknown
                 };
 
                 // calculate object size
                 let final_pos = stream.cursor.position();
                 let size = (final_pos - start_pos - 16) as u32;
 
-                // update the tag & size
+                // update the tag, size & unused field
                 stream.cursor.set_position(start_pos);
                 stream.save(tag)?;
                 stream.save(&size)?;
+                stream.save(&unused)?;
                 stream.cursor.set_position(final_pos);
 
                 Ok(())
@@ -146,11 +184,9 @@ fn tes3object_inherent_impls(idents: &[syn::Ident]) -> impl ToTokens {
     }
 }
 
-fn parse_variant_tags<'a, I>(variants: I) -> Vec<syn::LitStr>
-where
-    I: IntoIterator<Item = &'a syn::Variant>,
-{
-    variants.into_iter().map(|v| v.attrs[0].parse_args().unwrap()).collect()
+fn parse_variant_tag(variant: &syn::Variant) -> Option<syn::LitStr> {
+    let attr = variant.attrs.iter().find(|attr| attr.path().is_ident("tag"))?;
+    Some(attr.parse_args().unwrap())
 }
 
 fn parse_variant_idents<'a, I>(variants: I) -> Vec<syn::Ident>
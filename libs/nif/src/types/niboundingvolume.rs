@@ -31,7 +31,10 @@ impl Load for NiBoundingVolume {
             BoundType::Box => BoundData::NiBoxBV(stream.load()?),
             BoundType::Sphere => BoundData::NiSphereBV(stream.load()?),
             BoundType::Union => BoundData::NiUnionBV(stream.load()?),
-            _ => Reader::error(format!("Invalid BoundType: {bound_type:?}"))?,
+            _ => stream.fail(LoadErrorKind::InvalidValue {
+                name: "BoundType".into(),
+                value: format!("{bound_type:?}"),
+            })?,
         };
         Ok(Self { bound_data })
     }
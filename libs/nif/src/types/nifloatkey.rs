@@ -46,7 +46,10 @@ impl Load for NiFloatKey {
             KeyType::LinKey => NiFloatKey::LinKey(stream.load_vec(num_keys)?),
             KeyType::BezKey => NiFloatKey::BezKey(stream.load_vec(num_keys)?),
             KeyType::TCBKey => NiFloatKey::TCBKey(stream.load_vec(num_keys)?),
-            _ => Reader::error(format!("Invalid KeyType: {key_type:?}"))?,
+            _ => stream.fail(LoadErrorKind::InvalidValue {
+                name: "KeyType".into(),
+                value: format!("{key_type:?}"),
+            })?,
         })
     }
 }
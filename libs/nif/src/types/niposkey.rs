@@ -46,7 +46,10 @@ impl Load for NiPosKey {
             KeyType::LinKey => NiPosKey::LinKey(stream.load_vec(num_keys)?),
             KeyType::BezKey => NiPosKey::BezKey(stream.load_vec(num_keys)?),
             KeyType::TCBKey => NiPosKey::TCBKey(stream.load_vec(num_keys)?),
-            _ => Reader::error(format!("Invalid KeyType: {key_type:?}"))?,
+            _ => stream.fail(LoadErrorKind::InvalidValue {
+                name: "KeyType".into(),
+                value: format!("{key_type:?}"),
+            })?,
         })
     }
 }
@@ -49,7 +49,10 @@ impl Load for NiRotKey {
             KeyType::BezKey => NiRotKey::BezKey(stream.load_seq(num_keys)?),
             KeyType::TCBKey => NiRotKey::TCBKey(stream.load_seq(num_keys)?),
             KeyType::EulerKey => NiRotKey::EulerKey(stream.load()?),
-            _ => Reader::error(format!("Invalid KeyType: {key_type:?}"))?,
+            _ => stream.fail(LoadErrorKind::InvalidValue {
+                name: "KeyType".into(),
+                value: format!("{key_type:?}"),
+            })?,
         })
     }
 }
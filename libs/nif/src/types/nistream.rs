@@ -73,8 +73,10 @@ impl NiStream {
         self.objects.reserve(num_objects);
 
         // populate objects
-        for _ in 0..num_objects {
-            self.objects.insert(stream.load()?);
+        for index in 0..num_objects {
+            let start = stream.cursor.position();
+            let object = stream.load().map_err(|error| block_error(error, &stream, index, start))?;
+            self.objects.insert(object);
         }
 
         // allocate roots
@@ -349,17 +351,28 @@ impl NiStream {
     /// Yields all geometries and their world transforms.
     ///
     pub fn geometries<'a, T>(&'a self) -> impl Iterator<Item = (&'a T, Affine3A)>
+    where
+        &'a T: 'a + TryFrom<&'a NiType> + AsRef<NiGeometry>,
+    {
+        self.geometries_with_collision()
+            .map(|(geometry, transform, _)| (geometry, transform))
+    }
+
+    /// Yields all geometries and their world transforms, and whether they are beneath a
+    /// `RootCollisionNode`.
+    ///
+    fn geometries_with_collision<'a, T>(&'a self) -> impl Iterator<Item = (&'a T, Affine3A, bool)>
     where
         &'a T: 'a + TryFrom<&'a NiType> + AsRef<NiGeometry>,
     {
         let mut queue = VecDeque::new();
 
         for root in &self.roots {
-            queue.push_back((root.key, Affine3A::IDENTITY));
+            queue.push_back((root.key, Affine3A::IDENTITY, false));
         }
 
         std::iter::from_fn(move || {
-            while let Some((key, transform)) = queue.pop_front() {
+            while let Some((key, transform, collision)) = queue.pop_front() {
                 let Some(object) = self.objects.get(key) else {
                     continue;
                 };
@@ -367,9 +380,10 @@ impl NiStream {
                 if let Ok(node) = <&NiNode>::try_from(object) {
                     if !node.children.is_empty() {
                         let transform = transform * node.transform();
+                        let collision = collision || <&RootCollisionNode>::try_from(object).is_ok();
                         queue.reserve(node.children.len());
                         for child in &node.children {
-                            queue.push_back((child.key, transform));
+                            queue.push_back((child.key, transform, collision));
                         }
                     }
                     continue;
@@ -377,7 +391,7 @@ impl NiStream {
 
                 if let Ok(geometry) = <&T>::try_from(object) {
                     let transform = transform * geometry.as_ref().transform();
-                    return Some((geometry, transform));
+                    return Some((geometry, transform, collision));
                 };
             }
             None
@@ -402,6 +416,56 @@ impl NiStream {
         )
     }
 
+    /// Yields the triangles of all visible `NiTriShape` geometries, in world space.
+    ///
+    /// Geometry beneath a `RootCollisionNode` is only used for collision and is skipped, see
+    /// [`NiStream::collision_triangles`].
+    ///
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        self.tri_shape_triangles(|collision| !collision)
+    }
+
+    /// Yields the triangles the game uses for collision, in world space.
+    ///
+    /// As in the engine, only the geometry beneath the `RootCollisionNode` is used if the mesh has
+    /// one, and otherwise all visible geometry is. Meshes whose root has `NC` string data have no
+    /// collision, while `NCC` only disables camera collision and is ignored.
+    ///
+    /// Useful as the mesh source for `esp::PathGridGenerator`.
+    ///
+    pub fn collision_triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        let has_collision_node = self.objects_of_type::<RootCollisionNode>().next().is_some();
+        let no_collision =
+            self.root_has_string_data_starting_with("NC") && !self.root_has_string_data_starting_with("NCC");
+        self.tri_shape_triangles(move |collision| !no_collision && collision == has_collision_node)
+    }
+
+    fn tri_shape_triangles(&self, include: impl Fn(bool) -> bool + 'static) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        self.geometries_with_collision::<NiTriShape>()
+            .filter(move |(_, _, collision)| include(*collision))
+            .flat_map(move |(shape, transform, _)| {
+                let data = self.get_as::<_, NiTriShapeData>(shape.geometry_data);
+                data.into_iter().flat_map(move |data| {
+                    data.triangles.iter().filter_map(move |triangle| {
+                        let [a, b, c] = triangle.map(|i| data.vertices.get(i as usize).copied());
+                        Some([a?, b?, c?].map(|v| transform.transform_point3(v)))
+                    })
+                })
+            })
+    }
+
+    /// Yields the file names of all external textures, as written in the file.
+    ///
+    /// Useful for finding the textures a mesh needs, see `esp::AssetManifest`.
+    ///
+    pub fn texture_paths(&self) -> impl Iterator<Item = &str> + '_ {
+        self.objects_of_type::<NiSourceTexture>()
+            .filter_map(|texture| match &texture.source {
+                TextureSource::External(path) => Some(path.as_str()),
+                TextureSource::Internal(_) => None,
+            })
+    }
+
     /// Convenience function for case-insensitive prefix searches.
     ///
     pub fn root_has_string_data_starting_with(&self, prefix: &str) -> bool {
@@ -435,6 +499,19 @@ impl NiStream {
     }
 }
 
+/// Attach the location of the block starting at `start` to an error raised while loading it.
+fn block_error(error: io::Error, stream: &Reader<'_>, index: usize, start: u64) -> io::Error {
+    let mut error = LoadError::from(error);
+    error.offset.get_or_insert(stream.cursor.position());
+    error.record_index.get_or_insert(index);
+    if error.record_tag.is_none() {
+        let mut header = Reader::new(stream.cursor.get_ref());
+        header.cursor.set_position(start);
+        error.record_tag = header.load::<BString>().ok().map(|type_name| type_name.to_string());
+    }
+    error.into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
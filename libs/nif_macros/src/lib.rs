@@ -102,8 +102,10 @@ pub fn derive_nitype(input: TokenStream) -> TokenStream {
                         #(
                             #idents_bytes => Ok(Self::#idents(stream.load()?)),
                         )*
-                        // TODO: more detailed error information!
-                        _ => Reader::error(format!("Invalid Type: {}", type_name))?,
+                        _ => stream.fail(LoadErrorKind::InvalidValue {
+                            name: "Type".into(),
+                            value: type_name.to_string(),
+                        })?,
                     }
                 }
             }
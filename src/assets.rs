@@ -0,0 +1,56 @@
+// rust std imports
+use std::io;
+
+// external imports
+use esp::{AssetKind, AssetManifest, AssetUser, Plugin};
+use nif::NiStream;
+
+/// Build a manifest of every asset needed by `plugins`, including the textures used by meshes.
+///
+/// Meshes are read with `read`, given their path relative to `Data Files`. Meshes that cannot be
+/// read or parsed are returned alongside the manifest with their errors, as they are often
+/// provided by another mod or the base game.
+pub fn build_manifest<'a>(
+    plugins: impl IntoIterator<Item = &'a Plugin>,
+    read: impl FnMut(&str) -> io::Result<Vec<u8>>,
+) -> (AssetManifest, Vec<(String, io::Error)>) {
+    let mut manifest = AssetManifest::new();
+    for plugin in plugins {
+        manifest.add_plugin(plugin);
+    }
+    let errors = add_mesh_textures(&mut manifest, read);
+    (manifest, errors)
+}
+
+/// Add the textures used by each mesh in `manifest`, attributed to the records using the mesh.
+///
+/// Meshes are read with `read`, given their path relative to `Data Files`. Returns the meshes
+/// that could not be read or parsed, with their errors.
+pub fn add_mesh_textures(
+    manifest: &mut AssetManifest,
+    mut read: impl FnMut(&str) -> io::Result<Vec<u8>>,
+) -> Vec<(String, io::Error)> {
+    let meshes: Vec<_> = manifest.assets_of_kind(AssetKind::Mesh).cloned().collect();
+
+    let mut errors = vec![];
+    for mesh in meshes {
+        let stream = match read(&mesh.path).and_then(|bytes| NiStream::from_bytes(&bytes)) {
+            Ok(stream) => stream,
+            Err(error) => {
+                errors.push((mesh.path, error));
+                continue;
+            }
+        };
+        for texture in stream.texture_paths() {
+            for user in &mesh.users {
+                let user = AssetUser {
+                    mesh: Some(mesh.path.clone()),
+                    ..user.clone()
+                };
+                manifest.insert(AssetKind::Texture, texture, user);
+            }
+        }
+    }
+
+    errors
+}
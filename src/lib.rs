@@ -2,6 +2,10 @@
 //! A library for working with content from [The Elder Scrolls 3: Morrowind](https://en.wikipedia.org/wiki/The_Elder_Scrolls_III:_Morrowind).
 //!
 
+/// Module for working with `.bsa` archives.
+#[cfg(feature = "bsa")]
+pub use bsa;
+
 /// Module for working with `.esp` files.
 #[cfg(feature = "esp")]
 pub use esp;
@@ -9,3 +13,7 @@ pub use esp;
 /// Module for working with `.nif` files.
 #[cfg(feature = "nif")]
 pub use nif;
+
+/// Module for listing the asset files needed by plugins, including the textures used by meshes.
+#[cfg(all(feature = "esp", feature = "nif"))]
+pub mod assets;